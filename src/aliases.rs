@@ -0,0 +1,209 @@
+//! Expansion of the user-defined `[aliases]` from config.toml. An alias
+//! replaces the command word, so `linear mine --limit 5` with
+//! `mine = "issues list -a me"` runs `linear issues list -a me --limit 5`.
+
+use anyhow::Result;
+use clap::Command;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+
+use crate::error::CliError;
+
+/// Aliases may refer to other aliases, up to this depth
+const MAX_DEPTH: usize = 10;
+
+/// Replace a leading alias in `args` (program name first) with its expansion.
+/// Built-in commands always win over an alias of the same name.
+pub fn expand(
+    mut args: Vec<OsString>,
+    aliases: &BTreeMap<String, String>,
+    cli: &Command,
+) -> Result<Vec<OsString>> {
+    if aliases.is_empty() {
+        return Ok(args);
+    }
+
+    let mut seen = Vec::new();
+    while let Some(index) = command_index(&args, cli) {
+        let Some(name) = args[index].to_str().map(str::to_string) else {
+            break;
+        };
+        let Some(expansion) = aliases.get(&name).filter(|_| !is_builtin(cli, &name)) else {
+            break;
+        };
+        if seen.contains(&name) || seen.len() >= MAX_DEPTH {
+            return Err(CliError::new(
+                5,
+                format!("Alias '{}' expands to itself: {}", name, seen.join(" -> ")),
+            )
+            .into());
+        }
+        let words = split_words(expansion)
+            .map_err(|e| CliError::new(5, format!("Invalid alias '{}': {}", name, e)))?;
+        if words.is_empty() {
+            return Err(CliError::new(5, format!("Alias '{}' is empty", name)).into());
+        }
+        args.splice(index..=index, words.into_iter().map(OsString::from));
+        seen.push(name);
+    }
+    Ok(args)
+}
+
+/// True for subcommand names and their built-in aliases
+pub fn is_builtin(cli: &Command, name: &str) -> bool {
+    name == "help"
+        || cli
+            .get_subcommands()
+            .any(|c| c.get_name() == name || c.get_all_aliases().any(|a| a == name))
+}
+
+/// Position of the first word that isn't a global option or its value
+fn command_index(args: &[OsString], cli: &Command) -> Option<usize> {
+    let mut i = 1;
+    while i < args.len() {
+        let arg = args[i].to_str()?;
+        if arg == "--" {
+            return None;
+        }
+        if !arg.starts_with('-') || arg == "-" {
+            return Some(i);
+        }
+        // A separate value follows `--opt` and `-o`, but not `--opt=v` or `-ov`
+        let takes_value = if let Some(long) = arg.strip_prefix("--") {
+            !long.contains('=')
+                && cli
+                    .get_arguments()
+                    .any(|a| a.get_long() == Some(long) && a.get_action().takes_values())
+        } else {
+            let mut shorts = arg[1..].chars();
+            match (shorts.next(), shorts.next()) {
+                (Some(short), None) => cli
+                    .get_arguments()
+                    .any(|a| a.get_short() == Some(short) && a.get_action().takes_values()),
+                _ => false,
+            }
+        };
+        i += if takes_value { 2 } else { 1 };
+    }
+    None
+}
+
+/// Split an alias into words, honouring single and double quotes and
+/// backslash escapes
+pub fn split_words(input: &str) -> std::result::Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"') | None, '\\') => {
+                current.push(chars.next().ok_or("trailing backslash")?);
+                in_word = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if quote.is_some() {
+        return Err("unterminated quote".to_string());
+    }
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+
+    fn cli() -> Command {
+        Command::new("linear")
+            .arg(Arg::new("output").short('o').long("output"))
+            .arg(
+                Arg::new("quiet")
+                    .short('q')
+                    .long("quiet")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .subcommand(Command::new("issues").alias("i"))
+    }
+
+    fn args(words: &[&str]) -> Vec<OsString> {
+        words.iter().map(OsString::from).collect()
+    }
+
+    fn aliases(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_expand_after_global_options() {
+        let aliases = aliases(&[("mine", "issues list -a me")]);
+        let expanded = expand(
+            args(&["linear", "-o", "json", "-q", "mine", "--limit", "5"]),
+            &aliases,
+            &cli(),
+        )
+        .unwrap();
+        assert_eq!(
+            expanded,
+            args(&["linear", "-o", "json", "-q", "issues", "list", "-a", "me", "--limit", "5"])
+        );
+    }
+
+    #[test]
+    fn test_builtins_win_and_loops_are_errors() {
+        let shadow = aliases(&[("i", "issues list -a me")]);
+        let input = args(&["linear", "i", "list"]);
+        assert_eq!(expand(input.clone(), &shadow, &cli()).unwrap(), input);
+
+        let nested = aliases(&[("mine", "todo -o json"), ("todo", "issues list")]);
+        assert_eq!(
+            expand(args(&["linear", "mine"]), &nested, &cli()).unwrap(),
+            args(&["linear", "issues", "list", "-o", "json"])
+        );
+
+        let looping = aliases(&[("a", "b"), ("b", "a")]);
+        assert!(expand(args(&["linear", "a"]), &looping, &cli()).is_err());
+    }
+
+    #[test]
+    fn test_split_words_quotes() {
+        assert_eq!(
+            split_words(r#"issues list -s "In Progress" --format '{{identifier}} {{title}}'"#)
+                .unwrap(),
+            vec![
+                "issues",
+                "list",
+                "-s",
+                "In Progress",
+                "--format",
+                "{{identifier}} {{title}}"
+            ]
+        );
+        assert_eq!(split_words(r#"a\ b """#).unwrap(), vec!["a b", ""]);
+        assert!(split_words("'open").is_err());
+    }
+}
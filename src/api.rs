@@ -1,19 +1,21 @@
 use anyhow::{Context, Result};
 use reqwest::header::HeaderMap;
-use reqwest::{Client, StatusCode};
+use reqwest::{Certificate, Client, Proxy, StatusCode};
 use serde_json::{json, Value};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::cache::{Cache, CacheOptions, CacheType};
-use crate::config;
+use crate::cache::{self, Cache, CacheOptions, CacheType};
+use crate::config::{self, NetworkConfig};
 use crate::error::CliError;
 use crate::pagination::{paginate_nodes, PaginationOptions};
-use crate::retry::{with_retry, RetryConfig};
+use crate::rate_limit;
+use crate::recording::{self, TrafficMode};
+use crate::retry::{self, with_retry, RetryConfig};
 use crate::text::is_uuid;
+use crate::trace;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::OnceLock;
 
-const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
-
 /// Configuration for generic ID resolution
 struct ResolverConfig<'a> {
     cache_type: CacheType,
@@ -123,7 +125,7 @@ fn http_error(status: StatusCode, headers: &HeaderMap, context: &str) -> CliErro
     let retry_after = headers
         .get("retry-after")
         .and_then(|v| v.to_str().ok())
-        .and_then(|v| v.parse::<u64>().ok());
+        .and_then(retry::parse_retry_after);
     let request_id = headers
         .get("x-request-id")
         .and_then(|v| v.to_str().ok())
@@ -146,7 +148,8 @@ fn http_error(status: StatusCode, headers: &HeaderMap, context: &str) -> CliErro
                 status.as_u16(),
                 details["reason"].as_str().unwrap_or("Unknown error")
             ),
-        ),
+        )
+        .with_retry_after(retry_after.filter(|_| status.is_server_error())),
     };
     err.with_details(details)
 }
@@ -264,7 +267,7 @@ pub async fn resolve_label_id(
         paginated_query: r#"
             query($first: Int, $after: String) {
                 issueLabels(first: $first, after: $after) {
-                    nodes { id name }
+                    nodes { id name team { id } }
                     pageInfo { hasNextPage endCursor }
                 }
             }
@@ -294,6 +297,11 @@ pub async fn resolve_issue_id(
         );
     }
 
+    let key = issue.to_ascii_uppercase();
+    if let Some(id) = cache::lookup(CacheType::IssueIds, &key) {
+        return Ok(id);
+    }
+
     let query = r#"
         query($term: String!, $includeArchived: Boolean, $first: Int, $after: String) {
             searchIssues(term: $term, includeArchived: $includeArchived, first: $first, after: $after) {
@@ -328,6 +336,7 @@ pub async fn resolve_issue_id(
                 let identifier = node["identifier"].as_str().unwrap_or("");
                 if identifier.eq_ignore_ascii_case(issue) {
                     if let Some(id) = node["id"].as_str() {
+                        cache::remember(CacheType::IssueIds, &key, id);
                         return Ok(id.to_string());
                     }
                 }
@@ -352,7 +361,18 @@ pub async fn resolve_issue_id(
     anyhow::bail!("Issue not found: {}", issue)
 }
 
-/// Resolve a project name or UUID to a UUID.
+/// The slug ID in a project URL (`.../project/roadmap-2c4f7a1b9e0d/overview`),
+/// URL slug (`roadmap-2c4f7a1b9e0d`) or bare slug ID (`2c4f7a1b9e0d`)
+fn project_slug_id(project: &str) -> Option<&str> {
+    let slug = match project.split_once("/project/") {
+        Some((_, rest)) => rest.split('/').next().unwrap_or(rest),
+        None => project,
+    };
+    let slug_id = slug.rsplit('-').next().unwrap_or(slug);
+    (slug_id.len() == 12 && slug_id.chars().all(|c| c.is_ascii_hexdigit())).then_some(slug_id)
+}
+
+/// Resolve a project name, slug or UUID to a UUID.
 pub async fn resolve_project_id(
     client: &LinearClient,
     project: &str,
@@ -362,6 +382,32 @@ pub async fn resolve_project_id(
         return Ok(project.to_string());
     }
 
+    if let Some(slug) = project_slug_id(project) {
+        if let Some(id) = cache::lookup(CacheType::ProjectIds, slug) {
+            return Ok(id);
+        }
+        let query = r#"
+            query($slugId: String!) {
+                projects(first: 1, includeArchived: true, filter: { slugId: { eq: $slugId } }) {
+                    nodes { id }
+                }
+            }
+        "#;
+        let result = client.query(query, Some(json!({ "slugId": slug }))).await?;
+        if let Some(id) = result["data"]["projects"]["nodes"][0]["id"].as_str() {
+            cache::remember(CacheType::ProjectIds, slug, id);
+            return Ok(id.to_string());
+        }
+        // Not a slug after all; try it as a name
+    }
+
+    if let Some(id) = warm_cache()
+        .and_then(|cache| cache.get(CacheType::Projects))
+        .and_then(|projects| find_by_name(projects.as_array()?, project))
+    {
+        return Ok(id);
+    }
+
     let query = r#"
         query($name: String!, $includeArchived: Boolean) {
             projects(
@@ -510,6 +556,12 @@ pub async fn resolve_cycle_id(client: &LinearClient, team_id: &str, cycle: &str)
         return Ok(cycle.to_string());
     }
 
+    let numeric = cycle.parse::<i64>().ok();
+    let cycle_key = |number: i64| format!("{}:{}", team_id, number);
+    if let Some(id) = numeric.and_then(|n| cache::lookup(CacheType::CycleIds, &cycle_key(n))) {
+        return Ok(id);
+    }
+
     let query = r#"
         query($teamId: String!) {
             team(id: $teamId) {
@@ -532,7 +584,18 @@ pub async fn resolve_cycle_id(client: &LinearClient, team_id: &str, cycle: &str)
 
     let empty = vec![];
     let cycles = team["cycles"]["nodes"].as_array().unwrap_or(&empty);
-    let numeric = cycle.parse::<i64>().ok();
+    // One fetch covers every cycle of the team
+    let numbered: Vec<(String, String)> = cycles
+        .iter()
+        .filter_map(|c| {
+            Some((
+                cycle_key(c["number"].as_i64()?),
+                c["id"].as_str()?.to_string(),
+            ))
+        })
+        .collect();
+    cache::remember_all(CacheType::CycleIds, &numbered);
+
     for c in cycles {
         if let Some(id) = c["id"].as_str() {
             if let Some(num) = numeric {
@@ -555,6 +618,13 @@ pub async fn resolve_state_id(client: &LinearClient, team_id: &str, state: &str)
         return Ok(state.to_string());
     }
 
+    if let Some(id) = warm_cache()
+        .and_then(|cache| cache.get_keyed(CacheType::Statuses, team_id))
+        .and_then(|team| find_by_name(team["states"].as_array()?, state))
+    {
+        return Ok(id);
+    }
+
     let query = r#"
         query($teamId: String!) {
             team(id: $teamId) {
@@ -574,15 +644,10 @@ pub async fn resolve_state_id(client: &LinearClient, team_id: &str, state: &str)
         .as_array()
         .unwrap_or(&empty);
 
-    for s in states {
-        if s["name"].as_str().map(|n| n.eq_ignore_ascii_case(state)) == Some(true) {
-            if let Some(id) = s["id"].as_str() {
-                return Ok(id.to_string());
-            }
-        }
+    match find_by_name(states, state) {
+        Some(id) => Ok(id),
+        None => anyhow::bail!("State not found: {}", state),
     }
-
-    anyhow::bail!("State not found: {}", state)
 }
 
 /// Resolve label names or UUIDs to UUIDs for a team.
@@ -595,6 +660,28 @@ pub async fn resolve_label_ids(
         return Ok(vec![]);
     }
 
+    let cached = warm_cache()
+        .and_then(|cache| cache.get(CacheType::Labels))
+        .and_then(|labels| {
+            labels
+                .as_array()
+                .map(|labels| labels_for_team(labels, team_id))
+        })
+        .unwrap_or_default();
+    if let Some(resolved) = labels
+        .iter()
+        .map(|label| {
+            if is_uuid(label) {
+                Some(label.to_string())
+            } else {
+                find_by_name(&cached, label)
+            }
+        })
+        .collect::<Option<Vec<_>>>()
+    {
+        return Ok(resolved);
+    }
+
     let query = r#"
         query($teamId: String!) {
             team(id: $teamId) {
@@ -621,24 +708,51 @@ pub async fn resolve_label_ids(
             continue;
         }
 
-        let found = available
-            .iter()
-            .find(|l| l["name"].as_str().map(|n| n.eq_ignore_ascii_case(label)) == Some(true));
-
-        if let Some(l) = found {
-            if let Some(id) = l["id"].as_str() {
-                resolved.push(id.to_string());
-                continue;
-            }
+        match find_by_name(available, label) {
+            Some(id) => resolved.push(id),
+            None => anyhow::bail!("Label not found: {}", label),
         }
-
-        anyhow::bail!("Label not found: {}", label);
     }
 
     Ok(resolved)
 }
 
-fn find_team_id(teams: &[Value], team: &str) -> Option<String> {
+/// The cache for resolvers that read it directly, unless `--no-cache` is set
+fn warm_cache() -> Option<Cache> {
+    if cache::options().no_cache {
+        return None;
+    }
+    Cache::new().ok()
+}
+
+/// Cached labels usable on `team_id`'s issues. The cached list spans the
+/// workspace; only workspace labels and this team's labels apply. Entries
+/// cached without a team can't be scoped.
+pub fn labels_for_team(labels: &[Value], team_id: &str) -> Vec<Value> {
+    labels
+        .iter()
+        .filter(|l| {
+            l.get("team")
+                .is_some_and(|t| t.is_null() || t["id"].as_str() == Some(team_id))
+        })
+        .cloned()
+        .collect()
+}
+
+/// ID of the first node whose `name` matches case-insensitively
+fn find_by_name(nodes: &[Value], name: &str) -> Option<String> {
+    nodes
+        .iter()
+        .find(|n| {
+            n["name"]
+                .as_str()
+                .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        })
+        .and_then(|n| n["id"].as_str())
+        .map(|id| id.to_string())
+}
+
+pub fn find_team_id(teams: &[Value], team: &str) -> Option<String> {
     if let Some(team_data) = teams
         .iter()
         .find(|t| t["key"].as_str().map(|k| k.eq_ignore_ascii_case(team)) == Some(true))
@@ -660,7 +774,7 @@ fn find_team_id(teams: &[Value], team: &str) -> Option<String> {
     None
 }
 
-fn find_user_id(users: &[Value], user: &str) -> Option<String> {
+pub fn find_user_id(users: &[Value], user: &str) -> Option<String> {
     for u in users {
         let name = u["name"].as_str().unwrap_or("");
         let email = u["email"].as_str().unwrap_or("");
@@ -673,8 +787,7 @@ fn find_user_id(users: &[Value], user: &str) -> Option<String> {
     None
 }
 
-#[allow(dead_code)]
-fn find_label_id(labels: &[Value], label: &str) -> Option<String> {
+pub fn find_label_id(labels: &[Value], label: &str) -> Option<String> {
     for l in labels {
         let name = l["name"].as_str().unwrap_or("");
         if name.eq_ignore_ascii_case(label) {
@@ -689,49 +802,31 @@ fn find_label_id(labels: &[Value], label: &str) -> Option<String> {
 pub struct LinearClient {
     client: Client,
     api_key: String,
+    api_url: String,
     retry: RetryConfig,
 }
 
 impl LinearClient {
     pub fn new() -> Result<Self> {
-        let retry = default_retry_config();
-        let api_key = config::get_api_key()?;
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .connect_timeout(Duration::from_secs(10))
-            .user_agent(format!("linear-cli/{}", env!("CARGO_PKG_VERSION")))
-            .build()?;
-        Ok(Self {
-            client,
-            api_key,
-            retry,
-        })
+        Self::build(client_api_key()?, default_retry_config())
     }
 
     pub fn new_with_retry(retry_count: u32) -> Result<Self> {
-        let api_key = config::get_api_key()?;
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .connect_timeout(Duration::from_secs(10))
-            .user_agent(format!("linear-cli/{}", env!("CARGO_PKG_VERSION")))
-            .build()?;
-        Ok(Self {
-            client,
-            api_key,
-            retry: RetryConfig::new(retry_count),
-        })
+        Self::build(client_api_key()?, RetryConfig::new(retry_count))
     }
 
     pub fn with_api_key(api_key: String) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .connect_timeout(Duration::from_secs(10))
-            .user_agent(format!("linear-cli/{}", env!("CARGO_PKG_VERSION")))
-            .build()?;
+        Self::build(api_key, default_retry_config())
+    }
+
+    fn build(api_key: String, retry: RetryConfig) -> Result<Self> {
+        let network = config::network_config()?;
+        let timeout = Duration::from_secs(network.timeout_secs);
         Ok(Self {
-            client,
+            client: http_client(&network, Some(timeout))?,
             api_key,
-            retry: default_retry_config(),
+            api_url: config::api_url(),
+            retry,
         })
     }
 
@@ -739,61 +834,182 @@ impl LinearClient {
         let vars = variables.clone();
         with_retry(&self.retry, || {
             let vars = vars.clone();
-            async move { self.query_once(query, vars).await }
+            async move { self.query_once(query, vars, false).await }
         })
         .await
     }
 
-    async fn query_once(&self, query: &str, variables: Option<Value>) -> Result<Value> {
+    /// Like `query`, but a response carrying both `data` and `errors` is
+    /// returned as-is so callers can attribute errors to individual fields.
+    /// A response with errors and no data still fails.
+    pub async fn query_partial(&self, query: &str, variables: Option<Value>) -> Result<Value> {
+        let vars = variables.clone();
+        with_retry(&self.retry, || {
+            let vars = vars.clone();
+            async move { self.query_once(query, vars, true).await }
+        })
+        .await
+    }
+
+    async fn query_once(
+        &self,
+        query: &str,
+        variables: Option<Value>,
+        allow_partial: bool,
+    ) -> Result<Value> {
         let body = match variables {
             Some(vars) => json!({ "query": query, "variables": vars }),
             None => json!({ "query": query }),
         };
 
-        let response = self
-            .client
-            .post(LINEAR_API_URL)
-            .header("Content-Type", "application/json")
-            .header("Authorization", &self.api_key)
-            .json(&body)
-            .send()
-            .await?;
+        let started = Instant::now();
+        let (status, headers, text) = match recording::mode() {
+            Some(TrafficMode::Replay(dir)) => recording::replay(dir, &body)?,
+            _ => {
+                let response = match self
+                    .client
+                    .post(&self.api_url)
+                    .header("Content-Type", "application/json")
+                    .header("Authorization", &self.api_key)
+                    .json(&body)
+                    .send()
+                    .await
+                {
+                    Ok(response) => response,
+                    Err(e) => {
+                        trace::request(&body, None, started.elapsed(), None, Some(&e.to_string()));
+                        return Err(e.into());
+                    }
+                };
 
-        let status = response.status();
-        let headers = response.headers().clone();
+                let status = response.status();
+                let headers = response.headers().clone();
+                let text = response.text().await?;
+                if let Some(TrafficMode::Record(dir)) = recording::mode() {
+                    recording::record(dir, &body, status, &headers, &text)?;
+                }
+                (status, headers, text)
+            }
+        };
+
+        trace::request(
+            &body,
+            Some(status.as_u16()),
+            started.elapsed(),
+            headers.get("x-request-id").and_then(|v| v.to_str().ok()),
+            None,
+        );
+        rate_limit::record(&headers);
 
         // Check HTTP status before parsing JSON to avoid confusing errors
         if !status.is_success() {
             // Try to get error details from response body
-            let body = response.text().await.unwrap_or_default();
-            let details = if let Ok(json) = serde_json::from_str::<Value>(&body) {
+            let details = if let Ok(json) = serde_json::from_str::<Value>(&text) {
                 json
             } else {
-                json!({ "body": body })
+                json!({ "body": text })
             };
             let mut err = http_error(status, &headers, "resource");
-            if !body.is_empty() {
-                err = err.with_details(details);
+            // A classified GraphQL error says more than the status (Linear
+            // answers rate limiting and bad input with 400s)
+            let classified = details
+                .get("errors")
+                .filter(|e| e.as_array().is_some_and(|a| !a.is_empty()))
+                .map(CliError::from_graphql_errors)
+                .filter(|e| e.code != 1);
+            match classified {
+                Some(graphql_err) => err = graphql_err.with_retry_after(err.retry_after),
+                None if !text.is_empty() => err = err.with_details(details),
+                None => {}
             }
-            return Err(err.into());
+            return Err(with_rate_limit(err).into());
         }
 
-        let result: Value = response.json().await?;
+        let result: Value = serde_json::from_str(&text)?;
 
         if let Some(errors) = result.get("errors") {
-            return Err(CliError::new(1, "GraphQL error")
-                .with_details(errors.clone())
-                .into());
+            if allow_partial && result["data"].is_object() {
+                return Ok(result);
+            }
+            return Err(with_rate_limit(CliError::from_graphql_errors(errors)).into());
         }
 
         Ok(result)
     }
 
     pub async fn mutate(&self, mutation: &str, variables: Option<Value>) -> Result<Value> {
-        // Mutations are retried - Linear API is idempotent for creates/updates
+        // Updates are safe to retry; creates that must not be duplicated go
+        // through `create_entity` / `mutate_with_check` instead
         self.query(mutation, variables).await
     }
 
+    /// Run a mutation that may only take effect once.
+    ///
+    /// A failed attempt may still have been applied (e.g. the response timed
+    /// out), so before each retry `check_query` is run and passed to
+    /// `recover`; if that returns a response, the earlier attempt went through
+    /// and that response is returned instead of repeating the mutation.
+    pub async fn mutate_with_check<F>(
+        &self,
+        mutation: &str,
+        variables: Value,
+        check_query: &str,
+        check_variables: Value,
+        recover: F,
+    ) -> Result<Value>
+    where
+        F: Fn(&Value) -> Option<Value>,
+    {
+        let attempts = AtomicU32::new(0);
+        with_retry(&self.retry, || {
+            let variables = variables.clone();
+            let check_variables = check_variables.clone();
+            let attempts = &attempts;
+            let recover = &recover;
+            async move {
+                if attempts.fetch_add(1, Ordering::SeqCst) > 0 {
+                    if let Ok(existing) = self
+                        .query_once(check_query, Some(check_variables), false)
+                        .await
+                    {
+                        if let Some(response) = recover(&existing) {
+                            return Ok(response);
+                        }
+                    }
+                }
+                self.query_once(mutation, Some(variables), false).await
+            }
+        })
+        .await
+    }
+
+    /// Create one entity under a client-generated UUID so retries can't
+    /// duplicate it. `mutation` takes `$input` and returns
+    /// `{ <payload> { success <entity> { ... } } }`; `lookup` takes `$id` and
+    /// returns `{ <entity>(id: $id) { ... } }` with the same selection.
+    pub async fn create_entity(
+        &self,
+        mutation: &str,
+        mut input: Value,
+        payload: &str,
+        entity: &str,
+        lookup: &str,
+    ) -> Result<Value> {
+        let id = ensure_client_id(&mut input);
+        self.mutate_with_check(
+            mutation,
+            json!({ "input": input }),
+            lookup,
+            json!({ "id": id }),
+            |existing| {
+                let found = &existing["data"][entity];
+                (!found.is_null())
+                    .then(|| json!({ "data": { payload: { "success": true, entity: found } } }))
+            },
+        )
+        .await
+    }
+
     /// Fetch raw bytes from a URL with authorization header (for Linear uploads)
     pub async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>> {
         let response = self
@@ -819,6 +1035,76 @@ impl LinearClient {
     }
 }
 
+/// Attach the last known rate-limit budget to a rate-limit error's details
+fn with_rate_limit(mut err: CliError) -> CliError {
+    if err.code == 4 {
+        let details = err.details.get_or_insert_with(|| json!({}));
+        if let Some(obj) = details.as_object_mut() {
+            obj.insert("rate_limit".to_string(), json!(rate_limit::current()));
+        }
+    }
+    err
+}
+
+/// Give a create input a client-generated `id` unless it already has one
+pub fn ensure_client_id(input: &mut Value) -> String {
+    if let Some(id) = input["id"].as_str() {
+        return id.to_string();
+    }
+    let id = recording::client_id();
+    input["id"] = json!(id);
+    id
+}
+
+/// Client for file uploads: same proxy and CA settings as API calls, but no
+/// total timeout since large files can take longer than any API request
+pub fn upload_client() -> Result<Client> {
+    http_client(&config::network_config()?, None)
+}
+
+/// Build the HTTP client with the profile's proxy, CA bundle and timeouts
+fn http_client(network: &NetworkConfig, timeout: Option<Duration>) -> Result<Client> {
+    let mut builder = Client::builder()
+        .connect_timeout(Duration::from_secs(network.connect_timeout_secs))
+        .user_agent(format!("linear-cli/{}", env!("CARGO_PKG_VERSION")));
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+
+    if let Some(proxy) = &network.proxy {
+        let proxy = Proxy::all(proxy)
+            .map_err(|e| CliError::new(1, format!("Invalid proxy URL '{}': {}", proxy, e)))?;
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(path) = &network.ca_cert {
+        let pem = std::fs::read(path)
+            .with_context(|| format!("Failed to read CA bundle {}", path.display()))?;
+        let certs = Certificate::from_pem_bundle(&pem).map_err(|e| {
+            CliError::new(1, format!("Invalid CA bundle {}: {}", path.display(), e))
+        })?;
+        if certs.is_empty() {
+            return Err(
+                CliError::new(1, format!("No certificates found in {}", path.display())).into(),
+            );
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    Ok(builder.build()?)
+}
+
+/// API key for a new client; replaying a recording works without one.
+fn client_api_key() -> Result<String> {
+    match config::get_api_key() {
+        Ok(key) => Ok(key),
+        Err(_) if recording::is_replay() => Ok(String::new()),
+        Err(e) => Err(e),
+    }
+}
+
 static DEFAULT_RETRY: OnceLock<RetryConfig> = OnceLock::new();
 
 pub fn set_default_retry(retry_count: u32) {
@@ -833,3 +1119,23 @@ pub fn set_default_retry(retry_count: u32) {
 fn default_retry_config() -> RetryConfig {
     DEFAULT_RETRY.get().copied().unwrap_or_default()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_slug_id() {
+        assert_eq!(project_slug_id("2c4f7a1b9e0d"), Some("2c4f7a1b9e0d"));
+        assert_eq!(
+            project_slug_id("roadmap-2c4f7a1b9e0d"),
+            Some("2c4f7a1b9e0d")
+        );
+        assert_eq!(
+            project_slug_id("https://linear.app/acme/project/roadmap-2c4f7a1b9e0d/overview"),
+            Some("2c4f7a1b9e0d")
+        );
+        assert_eq!(project_slug_id("Roadmap"), None);
+        assert_eq!(project_slug_id("q3-planning"), None);
+    }
+}
@@ -0,0 +1,185 @@
+//! Fetch many objects by ID in as few requests as possible.
+//!
+//! IDs are split into chunks and each chunk becomes one query with an aliased
+//! root field per ID (`i0: issue(id: $i0) { ...IssueDetail }`). GraphQL errors
+//! are matched back to their alias through `path`, so one bad ID fails only
+//! its own entry while the rest of the chunk still comes back.
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use std::sync::OnceLock;
+
+use crate::api::LinearClient;
+use crate::error::CliError;
+
+/// IDs per request unless overridden with `--batch-size`
+pub const DEFAULT_CHUNK_SIZE: usize = 50;
+
+static CHUNK_SIZE: OnceLock<usize> = OnceLock::new();
+
+pub fn set_chunk_size(size: usize) {
+    let _ = CHUNK_SIZE.set(size.max(1));
+}
+
+fn chunk_size() -> usize {
+    CHUNK_SIZE.get().copied().unwrap_or(DEFAULT_CHUNK_SIZE)
+}
+
+/// A root field fetched by ID, with the fragment that selects its fields
+pub struct BatchQuery<'a> {
+    /// Root query field, e.g. `issue`
+    pub field: &'a str,
+    /// Fragment name spread into each aliased field, e.g. `IssueDetail`
+    pub fragment_name: &'a str,
+    /// Full fragment definition (`fragment IssueDetail on Issue { ... }`)
+    pub fragment: &'a str,
+    /// Used in not-found messages, e.g. `Issue`
+    pub what: &'a str,
+}
+
+/// Build the aliased query for one chunk of IDs
+fn build_query(spec: &BatchQuery, count: usize) -> String {
+    let params: Vec<String> = (0..count).map(|i| format!("$i{}: String!", i)).collect();
+    let fields: Vec<String> = (0..count)
+        .map(|i| {
+            format!(
+                "i{i}: {}(id: $i{i}) {{ ...{} }}",
+                spec.field, spec.fragment_name
+            )
+        })
+        .collect();
+    format!(
+        "query({}) {{\n    {}\n}}\n{}",
+        params.join(", "),
+        fields.join("\n    "),
+        spec.fragment
+    )
+}
+
+/// Fetch every ID, returning one result per ID in input order.
+///
+/// Request-level failures (auth, network, rate limits) fail the whole call;
+/// a missing object or a field error only fails its own entry.
+pub async fn fetch_by_ids(
+    client: &LinearClient,
+    spec: &BatchQuery<'_>,
+    ids: &[String],
+) -> Result<Vec<(String, Result<Value>)>> {
+    let mut results = Vec::with_capacity(ids.len());
+
+    for chunk in ids.chunks(chunk_size()) {
+        let query = build_query(spec, chunk.len());
+        let mut variables = Map::new();
+        for (i, id) in chunk.iter().enumerate() {
+            variables.insert(format!("i{}", i), json!(id));
+        }
+
+        let response = client
+            .query_partial(&query, Some(Value::Object(variables)))
+            .await?;
+        results.extend(split_response(spec, chunk, &response));
+    }
+
+    Ok(results)
+}
+
+/// Attribute each alias in a response back to its ID
+fn split_response(
+    spec: &BatchQuery,
+    ids: &[String],
+    response: &Value,
+) -> Vec<(String, Result<Value>)> {
+    let errors = response["errors"].as_array();
+
+    ids.iter()
+        .enumerate()
+        .map(|(i, id)| {
+            let alias = format!("i{}", i);
+            let value = &response["data"][&alias];
+            if !value.is_null() {
+                return (id.clone(), Ok(value.clone()));
+            }
+
+            let error = errors.and_then(|errors| {
+                errors
+                    .iter()
+                    .find(|e| e["path"][0].as_str() == Some(alias.as_str()))
+            });
+            let not_found = CliError::new(2, format!("{} not found: {}", spec.what, id));
+            let err = match error {
+                Some(error) => {
+                    let err = CliError::from_graphql_errors(&json!([error]));
+                    let message = error["message"].as_str().unwrap_or("GraphQL error");
+                    // A null lookup by ID whose error says so is a missing ID
+                    if err.code == 2 || message.to_lowercase().contains("not found") {
+                        not_found.with_details(error.clone())
+                    } else {
+                        CliError::new(err.code, message).with_details(error.clone())
+                    }
+                }
+                None => not_found,
+            };
+            (id.clone(), Err(err.into()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: BatchQuery = BatchQuery {
+        field: "issue",
+        fragment_name: "IssueRow",
+        fragment: "fragment IssueRow on Issue { id }",
+        what: "Issue",
+    };
+
+    #[test]
+    fn test_build_query_aliases_each_id() {
+        let query = build_query(&SPEC, 2);
+        assert!(query.starts_with("query($i0: String!, $i1: String!) {"));
+        assert!(query.contains("i0: issue(id: $i0) { ...IssueRow }"));
+        assert!(query.contains("i1: issue(id: $i1) { ...IssueRow }"));
+        assert!(query.ends_with("fragment IssueRow on Issue { id }"));
+    }
+
+    #[test]
+    fn test_split_response_keeps_per_id_errors() {
+        let ids = vec!["A-1".to_string(), "A-2".to_string(), "A-3".to_string()];
+        let response = json!({
+            "data": { "i0": { "id": "1" }, "i1": null, "i2": null },
+            "errors": [
+                { "message": "Entity not found: Issue", "path": ["i1"] },
+                { "message": "Server hiccup", "path": ["i2"] }
+            ]
+        });
+
+        let results = split_response(&SPEC, &ids, &response);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].1.as_ref().unwrap()["id"], "1");
+
+        let err = results[1].1.as_ref().unwrap_err();
+        let cli = err.downcast_ref::<CliError>().unwrap();
+        assert_eq!(cli.code, 2);
+        assert_eq!(cli.message, "Issue not found: A-2");
+
+        let err = results[2].1.as_ref().unwrap_err();
+        let cli = err.downcast_ref::<CliError>().unwrap();
+        assert_eq!(cli.code, 1);
+        assert_eq!(cli.message, "Server hiccup");
+    }
+
+    #[test]
+    fn test_split_response_null_without_error_is_not_found() {
+        let ids = vec!["A-1".to_string()];
+        let results = split_response(&SPEC, &ids, &json!({ "data": { "i0": null } }));
+        let cli = results[0]
+            .1
+            .as_ref()
+            .unwrap_err()
+            .downcast_ref::<CliError>()
+            .unwrap();
+        assert_eq!(cli.code, 2);
+    }
+}
@@ -1,16 +1,49 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
-use std::fs;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fs::{self, File, TryLockError};
 use std::io::Write;
-use std::path::PathBuf;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::config;
 
 /// Default cache TTL in seconds (1 hour)
 const DEFAULT_TTL_SECONDS: u64 = 3600;
 
+/// How long to wait for another process holding a cache lock
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// TTL for each key of the lookup caches (30 days). Issue identifiers, cycle
+/// numbers and project slugs never point somewhere else on Linear's side;
+/// the CLI drops entries itself when it moves an issue or renames a team key,
+/// and the TTL catches changes made elsewhere.
+pub const LOOKUP_TTL_SECONDS: u64 = 30 * 24 * 3600;
+
+/// Reads only re-stamp a key's last use when the stamp is older than this,
+/// so a hot lookup doesn't rewrite the file on every command
+const ACCESS_RESOLUTION_SECONDS: u64 = 3600;
+
+/// Temp files older than this were left by a writer that died
+const STALE_TEMP_SECONDS: u64 = 60;
+
+static OPTIONS: OnceLock<CacheOptions> = OnceLock::new();
+
+/// Prefetchable types a read found expired during this invocation
+static EXPIRED: Mutex<Vec<CacheType>> = Mutex::new(Vec::new());
+
+/// Record the global `--cache-ttl`/`--no-cache` flags for lookups that
+/// aren't handed options explicitly
+pub fn set_options(options: CacheOptions) {
+    let _ = OPTIONS.set(options);
+}
+
+pub fn options() -> CacheOptions {
+    OPTIONS.get().copied().unwrap_or_default()
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CacheOptions {
     pub ttl_seconds: Option<u64>,
@@ -32,25 +65,91 @@ pub struct CacheEntry {
     pub ttl_seconds: u64,
     /// The cached data
     pub data: Value,
+    /// Last use (unix seconds) of each key of a keyed cache, for LRU eviction
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accessed: BTreeMap<String, u64>,
+    /// When each key of a keyed cache was written; keys expire individually
+    /// `ttl_seconds` after this. Keys missing here date from `timestamp`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stored: BTreeMap<String, u64>,
+}
+
+fn now_seconds() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
+
+/// Drop the least recently used keys until at most `max_entries` remain;
+/// returns how many were dropped
+fn evict_lru(
+    map: &mut Map<String, Value>,
+    accessed: &mut BTreeMap<String, u64>,
+    max_entries: usize,
+) -> usize {
+    if map.len() <= max_entries {
+        return 0;
+    }
+    let mut keys: Vec<(u64, String)> = map
+        .keys()
+        .map(|k| (accessed.get(k).copied().unwrap_or(0), k.clone()))
+        .collect();
+    keys.sort();
+    let excess = map.len() - max_entries;
+    for (_, key) in keys.into_iter().take(excess) {
+        map.remove(&key);
+        accessed.remove(&key);
+    }
+    excess
 }
 
 impl CacheEntry {
     /// Check if the cache entry is still valid
     pub fn is_valid(&self) -> bool {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or(Duration::ZERO)
-            .as_secs();
-        now < self.timestamp + self.ttl_seconds
+        now_seconds() < self.timestamp + self.ttl_seconds
+    }
+
+    /// Check if one key of a keyed cache is still valid
+    pub fn key_is_valid(&self, key: &str) -> bool {
+        let stored = self.stored.get(key).copied().unwrap_or(self.timestamp);
+        now_seconds() < stored + self.ttl_seconds
+    }
+
+    /// Drop keys past their own TTL; returns how many were dropped
+    fn drop_expired_keys(&mut self) -> usize {
+        let Some(map) = self.data.as_object() else {
+            return 0;
+        };
+        let expired: Vec<String> = map
+            .keys()
+            .filter(|key| !self.key_is_valid(key))
+            .cloned()
+            .collect();
+        if let Some(map) = self.data.as_object_mut() {
+            for key in &expired {
+                map.remove(key);
+                self.accessed.remove(key);
+                self.stored.remove(key);
+            }
+        }
+        expired.len()
     }
 
     /// Get the age of the cache entry in seconds
     pub fn age_seconds(&self) -> u64 {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or(Duration::ZERO)
-            .as_secs();
-        now.saturating_sub(self.timestamp)
+        now_seconds().saturating_sub(self.timestamp)
+    }
+
+    /// Number of items: array elements, `nodes`, or keys of a keyed cache
+    pub fn item_count(&self) -> usize {
+        if let Some(items) = self.data.as_array() {
+            return items.len();
+        }
+        if let Some(nodes) = self.data.get("nodes").and_then(|n| n.as_array()) {
+            return nodes.len();
+        }
+        self.data.as_object().map(|o| o.len()).unwrap_or(1)
     }
 }
 
@@ -62,6 +161,12 @@ pub enum CacheType {
     Statuses,
     Labels,
     Projects,
+    /// Issue identifier (`ENG-123`) -> UUID
+    IssueIds,
+    /// `<team id>:<cycle number>` -> cycle ID
+    CycleIds,
+    /// Project slug ID -> project ID
+    ProjectIds,
 }
 
 impl CacheType {
@@ -73,6 +178,9 @@ impl CacheType {
             CacheType::Statuses => "statuses.json",
             CacheType::Labels => "labels.json",
             CacheType::Projects => "projects.json",
+            CacheType::IssueIds => "issue_ids.json",
+            CacheType::CycleIds => "cycle_ids.json",
+            CacheType::ProjectIds => "project_ids.json",
         }
     }
 
@@ -84,6 +192,9 @@ impl CacheType {
             CacheType::Statuses => "Statuses",
             CacheType::Labels => "Labels",
             CacheType::Projects => "Projects",
+            CacheType::IssueIds => "Issue IDs",
+            CacheType::CycleIds => "Cycle IDs",
+            CacheType::ProjectIds => "Project IDs",
         }
     }
 
@@ -95,14 +206,25 @@ impl CacheType {
             CacheType::Statuses,
             CacheType::Labels,
             CacheType::Projects,
+            CacheType::IssueIds,
+            CacheType::CycleIds,
+            CacheType::ProjectIds,
         ]
     }
+
+    /// Types holding whole lists that `cache refresh` can fetch up front;
+    /// the keyed lookup caches fill in as IDs are resolved
+    pub fn prefetchable() -> &'static [CacheType] {
+        &CacheType::all()[..5]
+    }
 }
 
 /// Cache manager for Linear CLI
 pub struct Cache {
     cache_dir: PathBuf,
     ttl_seconds: u64,
+    /// Keys kept per keyed cache before LRU eviction
+    max_entries: usize,
 }
 
 impl Cache {
@@ -118,6 +240,7 @@ impl Cache {
         Ok(Self {
             cache_dir,
             ttl_seconds,
+            max_entries: config::cache_max_entries()?,
         })
     }
 
@@ -139,82 +262,75 @@ impl Cache {
 
     /// Get cached data if valid
     pub fn get(&self, cache_type: CacheType) -> Option<Value> {
-        let path = self.cache_path(cache_type);
-        if !path.exists() {
-            return None;
-        }
-
-        let content = fs::read_to_string(&path).ok()?;
-        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        let entry = self.get_entry(cache_type)?;
 
         if entry.is_valid() {
             Some(entry.data)
         } else {
-            // Cache expired, remove it
-            let _ = fs::remove_file(&path);
+            note_expired(cache_type);
+            // Cache expired, remove it unless another process just refreshed it
+            let _ = self.remove_if(cache_type, |entry| entry.is_none_or(|e| !e.is_valid()));
             None
         }
     }
 
-    /// Get cache entry with metadata
+    /// Get cache entry with metadata. A file that doesn't parse (left behind
+    /// by a crash or an older version) is removed so the next write starts clean.
     pub fn get_entry(&self, cache_type: CacheType) -> Option<CacheEntry> {
         let path = self.cache_path(cache_type);
-        if !path.exists() {
-            return None;
+        let content = fs::read_to_string(&path).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(entry) => Some(entry),
+            Err(_) => {
+                let _ = self.remove_if(cache_type, |entry| entry.is_none());
+                None
+            }
         }
+    }
 
-        let content = fs::read_to_string(&path).ok()?;
-        serde_json::from_str(&content).ok()
+    /// Parse the entry on disk; `Ok(None)` if it is corrupt
+    fn read_entry(&self, cache_type: CacheType) -> Result<Option<CacheEntry>> {
+        let content = fs::read_to_string(self.cache_path(cache_type))?;
+        Ok(serde_json::from_str(&content).ok())
     }
 
-    /// Set cached data using atomic file writes
-    pub fn set(&self, cache_type: CacheType, data: Value) -> Result<()> {
+    /// Delete the file if `stale` still holds for it once the lock is held
+    fn remove_if(
+        &self,
+        cache_type: CacheType,
+        stale: impl FnOnce(Option<&CacheEntry>) -> bool,
+    ) -> Result<()> {
         let path = self.cache_path(cache_type);
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or(Duration::ZERO)
-            .as_secs();
-
-        let entry = CacheEntry {
-            timestamp,
-            ttl_seconds: self.ttl_seconds,
-            data,
+        let _lock = lock(&path)?;
+        let Ok(entry) = self.read_entry(cache_type) else {
+            return Ok(());
         };
-
-        let content = serde_json::to_string_pretty(&entry)?;
-
-        // Atomic write: write to temp file, sync, then rename
-        // Use secure permissions on Unix (0600)
-        let temp_path = path.with_extension("tmp");
-
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::OpenOptionsExt;
-            let mut file = fs::OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .mode(0o600)
-                .open(&temp_path)?;
-            file.write_all(content.as_bytes())?;
-            file.sync_all()?;
+        if stale(entry.as_ref()) {
+            fs::remove_file(&path)?;
         }
+        Ok(())
+    }
 
-        #[cfg(not(unix))]
-        {
-            let mut file = fs::File::create(&temp_path)?;
-            file.write_all(content.as_bytes())?;
-            file.sync_all()?;
-        }
+    /// Set cached data using atomic file writes
+    pub fn set(&self, cache_type: CacheType, data: Value) -> Result<()> {
+        let path = self.cache_path(cache_type);
+        let _lock = lock(&path)?;
+        self.write_raw(
+            cache_type,
+            &CacheEntry {
+                timestamp: now_seconds(),
+                ttl_seconds: self.ttl_seconds,
+                data,
+                accessed: BTreeMap::new(),
+                stored: BTreeMap::new(),
+            },
+        )
+    }
 
-        // Atomic rename
-        // On Windows, fs::rename fails if the destination exists, so remove it first
-        #[cfg(windows)]
-        {
-            let _ = fs::remove_file(&path);
-        }
-        fs::rename(&temp_path, &path)?;
-        Ok(())
+    /// Write `entry` as-is, keeping its timestamp and TTL; callers hold the lock
+    fn write_raw(&self, cache_type: CacheType, entry: &CacheEntry) -> Result<()> {
+        let content = serde_json::to_string_pretty(entry)?;
+        write_atomic(&self.cache_path(cache_type), content.as_bytes())
     }
 
     /// Clear cache for a specific type
@@ -236,19 +352,159 @@ impl Cache {
 
     /// Get cached data for a specific key within a cache type (e.g., statuses for a specific team)
     pub fn get_keyed(&self, cache_type: CacheType, key: &str) -> Option<Value> {
-        let data = self.get(cache_type)?;
-        data.get(key).cloned()
+        let entry = self.get_entry(cache_type)?;
+        if !entry.is_valid() {
+            note_expired(cache_type);
+            return None;
+        }
+        if !entry.key_is_valid(key) {
+            return None;
+        }
+        let value = entry.data.get(key).cloned()?;
+
+        let last_used = entry.accessed.get(key).copied().unwrap_or(0);
+        if now_seconds().saturating_sub(last_used) > ACCESS_RESOLUTION_SECONDS {
+            let _ = self.touch_keyed(cache_type, key);
+        }
+        Some(value)
+    }
+
+    /// Stamp `key` as used now. Only `accessed` changes: a read keeps the
+    /// entry's original timestamp and TTL, so it never extends validity.
+    fn touch_keyed(&self, cache_type: CacheType, key: &str) -> Result<()> {
+        let _lock = lock(&self.cache_path(cache_type))?;
+        let Some(mut entry) = self
+            .read_entry(cache_type)?
+            .filter(|entry| entry.is_valid() && entry.data.get(key).is_some())
+        else {
+            return Ok(());
+        };
+        entry.accessed.insert(key.to_string(), now_seconds());
+        self.write_raw(cache_type, &entry)
     }
 
     /// Set cached data for a specific key within a cache type
     pub fn set_keyed(&self, cache_type: CacheType, key: &str, value: Value) -> Result<()> {
-        let mut data = self.get(cache_type).unwrap_or_else(|| json!({}));
+        self.update_keyed(cache_type, |map| {
+            map.insert(key.to_string(), value);
+        })
+    }
 
-        if let Some(obj) = data.as_object_mut() {
-            obj.insert(key.to_string(), value);
+    /// Modify a keyed cache in place. Added or changed keys count as written
+    /// and used now, and the least recently used keys are evicted beyond the
+    /// size cap.
+    pub fn update_keyed(
+        &self,
+        cache_type: CacheType,
+        update: impl FnOnce(&mut Map<String, Value>),
+    ) -> Result<()> {
+        self.modify_keyed(cache_type, |map, _| update(map))
+    }
+
+    /// The read-modify-write happens under the lock, so concurrent writers
+    /// of different keys don't drop each other's entries. Keys past their
+    /// TTL are dropped first, so writing one again stamps it afresh.
+    fn modify_keyed(
+        &self,
+        cache_type: CacheType,
+        modify: impl FnOnce(&mut Map<String, Value>, &mut BTreeMap<String, u64>),
+    ) -> Result<()> {
+        let _lock = lock(&self.cache_path(cache_type))?;
+        let (mut data, mut accessed, mut stored) = match self
+            .read_entry(cache_type)
+            .ok()
+            .flatten()
+            .filter(|entry| entry.is_valid())
+        {
+            Some(mut entry) => {
+                entry.drop_expired_keys();
+                // Keys written before per-key stamps keep the file's age
+                for key in entry.data.as_object().into_iter().flat_map(|m| m.keys()) {
+                    entry.stored.entry(key.clone()).or_insert(entry.timestamp);
+                }
+                (entry.data, entry.accessed, entry.stored)
+            }
+            None => (json!({}), BTreeMap::new(), BTreeMap::new()),
+        };
+
+        if let Some(map) = data.as_object_mut() {
+            let before = map.clone();
+            modify(map, &mut accessed);
+            let now = now_seconds();
+            for (key, value) in map.iter() {
+                if before.get(key) != Some(value) {
+                    accessed.insert(key.clone(), now);
+                    stored.insert(key.clone(), now);
+                }
+            }
+            accessed.retain(|key, _| map.contains_key(key));
+            evict_lru(map, &mut accessed, self.max_entries);
+            stored.retain(|key, _| map.contains_key(key));
         }
 
-        self.set(cache_type, data)
+        self.write_raw(
+            cache_type,
+            &CacheEntry {
+                timestamp: now_seconds(),
+                ttl_seconds: self.ttl_seconds,
+                data,
+                accessed,
+                stored,
+            },
+        )
+    }
+
+    /// Remove expired and corrupt entries, trim keyed caches to the size cap
+    /// and delete temp files left by writers that died
+    fn prune(&self, report: &mut GcReport) -> Result<()> {
+        for &cache_type in CacheType::all() {
+            let path = self.cache_path(cache_type);
+            if !path.exists() {
+                continue;
+            }
+            let _lock = lock(&path)?;
+            match self.read_entry(cache_type) {
+                Ok(Some(mut entry)) if entry.is_valid() => {
+                    if !keyed(cache_type) || !entry.data.is_object() {
+                        continue;
+                    }
+                    let expired = entry.drop_expired_keys();
+                    let evicted = match entry.data.as_object_mut() {
+                        Some(map) => evict_lru(map, &mut entry.accessed, self.max_entries),
+                        None => 0,
+                    };
+                    if expired + evicted > 0 {
+                        report.expired += expired;
+                        report.evicted += evicted;
+                        if let Some(map) = entry.data.as_object() {
+                            entry.stored.retain(|key, _| map.contains_key(key));
+                        }
+                        // Keep the original age; pruning isn't a refresh
+                        self.write_raw(cache_type, &entry)?;
+                    }
+                }
+                Ok(_) => {
+                    fs::remove_file(&path)?;
+                    report.expired += 1;
+                }
+                Err(_) => {}
+            }
+        }
+
+        for entry in fs::read_dir(&self.cache_dir)?.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let old = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.elapsed().ok())
+                .is_some_and(|age| age.as_secs() > STALE_TEMP_SECONDS);
+            if name.starts_with('.') && name.ends_with(".tmp") && old {
+                fs::remove_file(entry.path())?;
+                report.temp_files += 1;
+            }
+        }
+        Ok(())
     }
 
     /// Get cache status for all types
@@ -260,19 +516,7 @@ impl Cache {
                 let (valid, age_seconds, size_bytes, item_count) = if path.exists() {
                     if let Some(entry) = self.get_entry(*cache_type) {
                         let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
-                        let count = entry
-                            .data
-                            .as_array()
-                            .map(|a| a.len())
-                            .or_else(|| {
-                                // Handle nested nodes structure
-                                entry
-                                    .data
-                                    .get("nodes")
-                                    .and_then(|n| n.as_array())
-                                    .map(|a| a.len())
-                            })
-                            .unwrap_or(1);
+                        let count = entry.item_count();
                         (
                             entry.is_valid(),
                             Some(entry.age_seconds()),
@@ -298,6 +542,243 @@ impl Cache {
     }
 }
 
+fn note_expired(cache_type: CacheType) {
+    if !CacheType::prefetchable().contains(&cache_type) {
+        return;
+    }
+    if let Ok(mut expired) = EXPIRED.lock() {
+        if !expired.contains(&cache_type) {
+            expired.push(cache_type);
+        }
+    }
+}
+
+/// Prefetchable types found expired while this command resolved names, for
+/// a background refresh once it is done
+pub fn expired_types() -> Vec<CacheType> {
+    EXPIRED
+        .lock()
+        .map(|expired| expired.clone())
+        .unwrap_or_default()
+}
+
+/// Cached ID for `key` in one of the keyed lookup caches
+pub fn lookup(cache_type: CacheType, key: &str) -> Option<String> {
+    if options().no_cache {
+        return None;
+    }
+    Cache::with_ttl(LOOKUP_TTL_SECONDS)
+        .ok()?
+        .get_keyed(cache_type, key)?
+        .as_str()
+        .map(|s| s.to_string())
+}
+
+/// Remember `key -> id` in a keyed lookup cache; failures are ignored
+pub fn remember(cache_type: CacheType, key: &str, id: &str) {
+    remember_all(cache_type, &[(key.to_string(), id.to_string())]);
+}
+
+/// Remember several mappings with a single write
+pub fn remember_all(cache_type: CacheType, pairs: &[(String, String)]) {
+    if options().no_cache || pairs.is_empty() {
+        return;
+    }
+    if let Ok(cache) = Cache::with_ttl(LOOKUP_TTL_SECONDS) {
+        let _ = cache.update_keyed(cache_type, |map| {
+            for (key, id) in pairs {
+                map.insert(key.clone(), json!(id));
+            }
+        });
+    }
+}
+
+/// Drop lookup entries matching `stale(key, id)`
+pub fn forget(cache_type: CacheType, stale: impl Fn(&str, &str) -> bool) {
+    if let Ok(cache) = Cache::with_ttl(LOOKUP_TTL_SECONDS) {
+        let _ = cache.update_keyed(cache_type, |map| {
+            map.retain(|key, id| !stale(key, id.as_str().unwrap_or_default()));
+        });
+    }
+}
+
+/// Caches keyed by team, identifier etc. rather than holding one list
+fn keyed(cache_type: CacheType) -> bool {
+    matches!(
+        cache_type,
+        CacheType::Statuses | CacheType::IssueIds | CacheType::CycleIds | CacheType::ProjectIds
+    )
+}
+
+/// What `cache gc` cleaned up
+#[derive(Debug, Default, Serialize)]
+pub struct GcReport {
+    /// Expired or unreadable cache files, and expired keys of keyed caches,
+    /// removed
+    pub expired: usize,
+    /// Keys evicted from keyed caches over the size cap
+    pub evicted: usize,
+    /// Temp files left behind by interrupted writes
+    pub temp_files: usize,
+    /// Cache directories of profiles that no longer exist
+    pub orphaned_profiles: Vec<String>,
+    /// Orphaned profiles kept because their outbox still has queued writes
+    pub kept_with_outbox: Vec<String>,
+}
+
+/// Prune every profile's cache and delete the directories of profiles that
+/// were removed from the config
+pub fn gc() -> Result<GcReport> {
+    let root = cache_dir_path()?
+        .parent()
+        .map(Path::to_path_buf)
+        .context("Could not find cache directory")?;
+    let config = config::load_config()?;
+    let current = config::current_profile().unwrap_or_else(|_| "default".to_string());
+    let max_entries = config::cache_max_entries()?;
+
+    let mut report = GcReport::default();
+    let Ok(dirs) = fs::read_dir(&root) else {
+        return Ok(report);
+    };
+    for dir in dirs.flatten().filter(|d| d.path().is_dir()) {
+        let profile = dir.file_name().to_string_lossy().to_string();
+        // "default" holds the cache of env-var/legacy keys, which have no profile entry
+        if profile == current || profile == "default" || config.workspaces.contains_key(&profile) {
+            let cache = Cache {
+                cache_dir: dir.path(),
+                ttl_seconds: DEFAULT_TTL_SECONDS,
+                max_entries,
+            };
+            cache.prune(&mut report)?;
+        } else if has_queued_writes(&dir.path()) {
+            report.kept_with_outbox.push(profile);
+        } else {
+            fs::remove_dir_all(dir.path())?;
+            report.orphaned_profiles.push(profile);
+        }
+    }
+    Ok(report)
+}
+
+fn has_queued_writes(profile_dir: &Path) -> bool {
+    fs::read_to_string(profile_dir.join("outbox.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Vec<Value>>(&content).ok())
+        .is_some_and(|entries| !entries.is_empty())
+}
+
+/// Exclusive advisory lock on `<path>.lock`, released when dropped
+pub struct FileLock {
+    _file: File,
+}
+
+/// Lock `path` against other linear-cli processes. Waits up to
+/// [`LOCK_TIMEOUT`] for a process that holds it; the wait blocks the calling
+/// thread, so on the multi-threaded runtime it runs via `block_in_place` to
+/// keep other tasks moving.
+pub fn lock(path: &Path) -> Result<FileLock> {
+    let lock_path = path.with_extension("lock");
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Could not open lock file {}", lock_path.display()))?;
+
+    match file.try_lock() {
+        Ok(()) => return Ok(FileLock { _file: file }),
+        Err(TryLockError::WouldBlock) => {}
+        Err(TryLockError::Error(e)) => {
+            return Err(e).with_context(|| format!("Could not lock {}", lock_path.display()))
+        }
+    }
+
+    let started = Instant::now();
+    blocking(|| loop {
+        std::thread::sleep(Duration::from_millis(10));
+        match file.try_lock() {
+            Ok(()) => return Ok(()),
+            Err(TryLockError::WouldBlock) if started.elapsed() < LOCK_TIMEOUT => {}
+            Err(TryLockError::WouldBlock) => {
+                anyhow::bail!(
+                    "Timed out waiting for {} (another linear-cli process holds it)",
+                    lock_path.display()
+                );
+            }
+            Err(TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("Could not lock {}", lock_path.display()))
+            }
+        }
+    })?;
+    Ok(FileLock { _file: file })
+}
+
+/// Run `f`, which may sleep, without stalling the tokio worker it's called on.
+/// Cache calls come from async commands as well as plain threads.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    use tokio::runtime::{Handle, RuntimeFlavor};
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        _ => f(),
+    }
+}
+
+/// Replace `path` with `content` so readers see either the old or the new
+/// file, never a partial one. The temp file name is unique per writer, so
+/// concurrent writers can't interleave into the same temp file.
+/// Permissions are 0600 on Unix.
+pub fn write_atomic(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("cache");
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}-{:08x}.tmp",
+        file_name,
+        std::process::id(),
+        rand::random::<u32>()
+    ));
+
+    let write = || -> Result<()> {
+        #[cfg(unix)]
+        let mut file = {
+            use std::os::unix::fs::OpenOptionsExt;
+            fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&temp_path)?
+        };
+        #[cfg(not(unix))]
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+
+        // On Windows, fs::rename fails if the destination exists, so remove it first
+        #[cfg(windows)]
+        {
+            let _ = fs::remove_file(path);
+        }
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    };
+
+    let result = write();
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
 pub fn cache_dir_path() -> Result<PathBuf> {
     let profile = config::current_profile().unwrap_or_else(|_| "default".to_string());
     let config_dir = dirs::config_dir()
@@ -344,6 +825,217 @@ impl CacheStatus {
 mod tests {
     use super::*;
 
+    fn temp_cache(name: &str) -> Cache {
+        let cache_dir = std::env::temp_dir().join(format!(
+            "linear-cli-cache-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&cache_dir);
+        fs::create_dir_all(&cache_dir).unwrap();
+        Cache {
+            cache_dir,
+            ttl_seconds: 3600,
+            max_entries: config::DEFAULT_CACHE_MAX_ENTRIES,
+        }
+    }
+
+    #[test]
+    fn test_corrupt_entry_is_removed() {
+        let cache = temp_cache("corrupt");
+        let path = cache.cache_path(CacheType::Teams);
+        fs::write(&path, "{\"timestamp\": 1, \"da").unwrap();
+        assert!(cache.get(CacheType::Teams).is_none());
+        assert!(!path.exists());
+
+        cache
+            .set(CacheType::Teams, json!([{ "id": "t1" }]))
+            .unwrap();
+        assert_eq!(cache.get(CacheType::Teams), Some(json!([{ "id": "t1" }])));
+        let _ = fs::remove_dir_all(&cache.cache_dir);
+    }
+
+    #[test]
+    fn test_concurrent_set_keyed_keeps_every_key() {
+        let cache = std::sync::Arc::new(temp_cache("keyed"));
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                let cache = std::sync::Arc::clone(&cache);
+                std::thread::spawn(move || {
+                    cache
+                        .set_keyed(CacheType::Statuses, &format!("team-{}", i), json!(i))
+                        .unwrap();
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        let data = cache.get(CacheType::Statuses).unwrap();
+        assert_eq!(data.as_object().unwrap().len(), 8);
+        let leftovers = fs::read_dir(&cache.cache_dir)
+            .unwrap()
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+            .count();
+        assert_eq!(leftovers, 0);
+        let _ = fs::remove_dir_all(&cache.cache_dir);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_contended_lock_waits_off_the_runtime_worker() {
+        let cache = temp_cache("lock");
+        let path = cache.cache_path(CacheType::Teams);
+        let held = lock(&path).unwrap();
+        let waiter = {
+            let path = path.clone();
+            tokio::spawn(async move { lock(&path).map(|_| ()) })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // The only worker isn't stuck in the waiter's sleep loop
+        let other = tokio::spawn(async { 1 });
+        let ran = tokio::time::timeout(Duration::from_secs(2), other).await;
+        drop(held);
+        assert_eq!(ran.unwrap().unwrap(), 1);
+        waiter.await.unwrap().unwrap();
+        let _ = fs::remove_dir_all(&cache.cache_dir);
+    }
+
+    #[test]
+    fn test_keyed_cache_evicts_least_recently_used() {
+        let mut cache = temp_cache("lru");
+        cache.max_entries = 2;
+        cache
+            .set_keyed(CacheType::IssueIds, "A-1", json!("a"))
+            .unwrap();
+        cache
+            .set_keyed(CacheType::IssueIds, "A-2", json!("b"))
+            .unwrap();
+        // Age A-2 so the read of A-1 below makes A-2 the least recently used
+        cache
+            .modify_keyed(CacheType::IssueIds, |_, accessed| {
+                accessed.insert("A-1".to_string(), 1);
+                accessed.insert("A-2".to_string(), 2);
+            })
+            .unwrap();
+        assert_eq!(
+            cache.get_keyed(CacheType::IssueIds, "A-1"),
+            Some(json!("a"))
+        );
+        cache
+            .set_keyed(CacheType::IssueIds, "A-3", json!("c"))
+            .unwrap();
+
+        assert_eq!(cache.get_keyed(CacheType::IssueIds, "A-2"), None);
+        assert_eq!(
+            cache.get_keyed(CacheType::IssueIds, "A-1"),
+            Some(json!("a"))
+        );
+        assert_eq!(
+            cache.get_keyed(CacheType::IssueIds, "A-3"),
+            Some(json!("c"))
+        );
+        let _ = fs::remove_dir_all(&cache.cache_dir);
+    }
+
+    #[test]
+    fn test_keyed_read_does_not_extend_validity() {
+        // A cache opened with a longer TTL than the entry was written with
+        let mut cache = temp_cache("touch");
+        cache.ttl_seconds = LOOKUP_TTL_SECONDS;
+        let written = now_seconds() - 2 * ACCESS_RESOLUTION_SECONDS;
+        let entry = CacheEntry {
+            timestamp: written,
+            ttl_seconds: 3 * ACCESS_RESOLUTION_SECONDS,
+            data: json!({ "A-1": "a" }),
+            accessed: BTreeMap::from([("A-1".to_string(), written)]),
+            stored: BTreeMap::new(),
+        };
+        cache.write_raw(CacheType::IssueIds, &entry).unwrap();
+
+        assert_eq!(
+            cache.get_keyed(CacheType::IssueIds, "A-1"),
+            Some(json!("a"))
+        );
+        let after = cache.get_entry(CacheType::IssueIds).unwrap();
+        assert!(after.accessed["A-1"] > written);
+        assert_eq!(after.timestamp, written);
+        assert_eq!(after.ttl_seconds, 3 * ACCESS_RESOLUTION_SECONDS);
+        let _ = fs::remove_dir_all(&cache.cache_dir);
+    }
+
+    #[test]
+    fn test_lookup_keys_expire_individually() {
+        let cache = temp_cache("per-key");
+        let now = now_seconds();
+        let entry = CacheEntry {
+            timestamp: now,
+            ttl_seconds: 3600,
+            data: json!({ "A-1": "old", "A-2": "new" }),
+            accessed: BTreeMap::new(),
+            stored: BTreeMap::from([("A-1".to_string(), now - 7200), ("A-2".to_string(), now)]),
+        };
+        cache.write_raw(CacheType::IssueIds, &entry).unwrap();
+        assert_eq!(cache.get_keyed(CacheType::IssueIds, "A-1"), None);
+        assert_eq!(
+            cache.get_keyed(CacheType::IssueIds, "A-2"),
+            Some(json!("new"))
+        );
+
+        // Writing another key doesn't renew the expired one
+        cache
+            .set_keyed(CacheType::IssueIds, "A-3", json!("c"))
+            .unwrap();
+        let after = cache.get_entry(CacheType::IssueIds).unwrap();
+        assert!(after.data.get("A-1").is_none());
+        assert_eq!(after.stored["A-2"], now);
+
+        // Remembering it again stamps it afresh, even with the same value
+        cache
+            .set_keyed(CacheType::IssueIds, "A-1", json!("old"))
+            .unwrap();
+        assert_eq!(
+            cache.get_keyed(CacheType::IssueIds, "A-1"),
+            Some(json!("old"))
+        );
+        let _ = fs::remove_dir_all(&cache.cache_dir);
+    }
+
+    #[test]
+    fn test_prune_removes_expired_and_trims_keyed() {
+        let mut cache = temp_cache("prune");
+        cache.set(CacheType::Teams, json!([])).unwrap();
+        let expired = CacheEntry {
+            timestamp: 0,
+            ttl_seconds: 60,
+            data: json!([]),
+            accessed: BTreeMap::new(),
+            stored: BTreeMap::new(),
+        };
+        fs::write(
+            cache.cache_path(CacheType::Users),
+            serde_json::to_string(&expired).unwrap(),
+        )
+        .unwrap();
+        for i in 0..5 {
+            cache
+                .set_keyed(CacheType::CycleIds, &format!("t:{}", i), json!(i))
+                .unwrap();
+        }
+        cache.max_entries = 3;
+
+        let mut report = GcReport::default();
+        cache.prune(&mut report).unwrap();
+        assert_eq!(report.expired, 1);
+        assert_eq!(report.evicted, 2);
+        assert!(cache.get(CacheType::Teams).is_some());
+        assert!(!cache.cache_path(CacheType::Users).exists());
+        let cycles = cache.get(CacheType::CycleIds).unwrap();
+        assert_eq!(cycles.as_object().unwrap().len(), 3);
+        let _ = fs::remove_dir_all(&cache.cache_dir);
+    }
+
     #[test]
     fn test_cache_entry_validity() {
         let entry = CacheEntry {
@@ -353,6 +1045,8 @@ mod tests {
                 .as_secs(),
             ttl_seconds: 3600,
             data: serde_json::json!({"test": "data"}),
+            accessed: BTreeMap::new(),
+            stored: BTreeMap::new(),
         };
         assert!(entry.is_valid());
     }
@@ -363,6 +1057,8 @@ mod tests {
             timestamp: 0, // Very old timestamp
             ttl_seconds: 3600,
             data: serde_json::json!({"test": "data"}),
+            accessed: BTreeMap::new(),
+            stored: BTreeMap::new(),
         };
         assert!(!entry.is_valid());
     }
@@ -377,6 +1073,8 @@ mod tests {
             timestamp: now - 60, // 60 seconds ago
             ttl_seconds: 3600,
             data: serde_json::json!({}),
+            accessed: BTreeMap::new(),
+            stored: BTreeMap::new(),
         };
         let age = entry.age_seconds();
         assert!((60..=62).contains(&age)); // Allow small drift
@@ -389,6 +1087,7 @@ mod tests {
         assert_eq!(CacheType::Statuses.filename(), "statuses.json");
         assert_eq!(CacheType::Labels.filename(), "labels.json");
         assert_eq!(CacheType::Projects.filename(), "projects.json");
+        assert_eq!(CacheType::IssueIds.filename(), "issue_ids.json");
     }
 
     #[test]
@@ -400,7 +1099,8 @@ mod tests {
     #[test]
     fn test_cache_type_all() {
         let all = CacheType::all();
-        assert_eq!(all.len(), 5);
+        assert_eq!(all.len(), 8);
+        assert!(!CacheType::prefetchable().contains(&CacheType::IssueIds));
     }
 
     #[test]
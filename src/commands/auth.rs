@@ -1,10 +1,11 @@
 use anyhow::Result;
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
 use dialoguer::{Confirm, Password};
 use serde_json::json;
 
 use crate::api::LinearClient;
 use crate::config;
+use crate::keystore;
 use crate::output::{print_json, OutputOptions};
 
 #[derive(Subcommand)]
@@ -18,8 +19,12 @@ pub enum AuthCommands {
         #[arg(long)]
         validate: bool,
         /// Store in OS keyring instead of config file (requires secure-storage feature)
-        #[arg(long)]
+        #[arg(long, conflicts_with = "encrypted")]
         secure: bool,
+        /// Store in the passphrase-encrypted key file (passphrase from prompt or
+        /// LINEAR_CLI_PASSPHRASE)
+        #[arg(long)]
+        encrypted: bool,
     },
     /// Remove API key for the current profile
     Logout {
@@ -33,9 +38,15 @@ pub enum AuthCommands {
         #[arg(long)]
         validate: bool,
     },
-    /// Migrate API keys from config file to OS keyring
-    #[cfg(feature = "secure-storage")]
+    /// Migrate API keys from config file to OS keyring or the encrypted key file
+    #[command(after_help = r#"EXAMPLES:
+    linear auth migrate                        # Move keys to the OS keyring
+    linear auth migrate --to file              # Move keys to the encrypted key file
+    LINEAR_CLI_PASSPHRASE=... linear auth migrate --to file --force"#)]
     Migrate {
+        /// Where to move the keys
+        #[arg(long, value_enum, default_value_t = MigrateTarget::default())]
+        to: MigrateTarget,
         /// Keep keys in config file after migrating
         #[arg(long)]
         keep_config: bool,
@@ -45,17 +56,40 @@ pub enum AuthCommands {
     },
 }
 
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum MigrateTarget {
+    /// OS keyring (requires the secure-storage feature)
+    Keyring,
+    /// Passphrase-encrypted key file next to config.toml
+    File,
+}
+
+impl Default for MigrateTarget {
+    /// The keyring when it's compiled in, otherwise the encrypted file
+    fn default() -> Self {
+        if cfg!(feature = "secure-storage") {
+            MigrateTarget::Keyring
+        } else {
+            MigrateTarget::File
+        }
+    }
+}
+
 pub async fn handle(cmd: AuthCommands, output: &OutputOptions) -> Result<()> {
     match cmd {
         AuthCommands::Login {
             key,
             validate,
             secure,
-        } => login(key, validate, secure, output).await,
+            encrypted,
+        } => login(key, validate, secure, encrypted, output).await,
         AuthCommands::Logout { force } => logout(force, output).await,
         AuthCommands::Status { validate } => status(validate, output).await,
-        #[cfg(feature = "secure-storage")]
-        AuthCommands::Migrate { keep_config, force } => migrate(keep_config, force, output).await,
+        AuthCommands::Migrate {
+            to,
+            keep_config,
+            force,
+        } => migrate(to, keep_config, force, output).await,
     }
 }
 
@@ -63,6 +97,7 @@ async fn login(
     key: Option<String>,
     validate: bool,
     secure: bool,
+    encrypted: bool,
     output: &OutputOptions,
 ) -> Result<()> {
     let key = match key {
@@ -101,6 +136,30 @@ async fn login(
         anyhow::bail!("Secure storage requires the 'secure-storage' feature. Rebuild with: cargo build --features secure-storage");
     }
 
+    if encrypted {
+        keystore::set_key(&profile, &key)?;
+        // Keep the profile listed, without the plaintext key
+        config::set_workspace_key(&profile, "")?;
+
+        if output.is_json() || output.has_template() {
+            print_json(
+                &json!({
+                    "profile": profile,
+                    "saved": true,
+                    "storage": "encrypted-file"
+                }),
+                output,
+            )?;
+            return Ok(());
+        }
+
+        println!(
+            "API key saved to the encrypted key file for profile '{}'",
+            profile
+        );
+        return Ok(());
+    }
+
     config::set_workspace_key(&profile, &key)?;
 
     if output.is_json() || output.has_template() {
@@ -140,6 +199,7 @@ async fn logout(force: bool, output: &OutputOptions) -> Result<()> {
     {
         let _ = crate::keyring::delete_key(&profile); // Ignore errors (may not exist)
     }
+    keystore::delete_key(&profile)?;
 
     config::workspace_remove(&profile)?;
 
@@ -173,6 +233,10 @@ async fn status(validate: bool, output: &OutputOptions) -> Result<()> {
         .and_then(|p| config_data.workspaces.get(p))
         .map(|w| !w.api_key.is_empty())
         .unwrap_or(false);
+    let api_key_command = profile
+        .as_ref()
+        .and_then(|p| config_data.workspaces.get(p))
+        .and_then(|w| w.api_key_command.clone());
 
     // Check keyring storage
     #[cfg(feature = "secure-storage")]
@@ -189,21 +253,12 @@ async fn status(validate: bool, output: &OutputOptions) -> Result<()> {
     #[cfg(not(feature = "secure-storage"))]
     let keyring_available = false;
 
+    let encrypted_file = profile.as_deref().is_some_and(keystore::has_key);
+
     let mut validated = None;
     if validate {
-        // Try to get key using the priority: env > keyring > config
-        let key = env_key.clone().or_else(|| {
-            #[cfg(feature = "secure-storage")]
-            if let Some(ref p) = profile {
-                if let Ok(Some(k)) = crate::keyring::get_key(p) {
-                    return Some(k);
-                }
-            }
-            profile
-                .as_ref()
-                .and_then(|p| config_data.workspaces.get(p))
-                .map(|w| w.api_key.clone())
-        });
+        // Same priority as every other command: env > keyring > credential helper > config
+        let key = config::get_api_key().ok().filter(|k| !k.is_empty());
         validated = match key {
             Some(key) => Some(validate_key(&key).await.is_ok()),
             None => Some(false),
@@ -215,8 +270,10 @@ async fn status(validate: bool, output: &OutputOptions) -> Result<()> {
             &json!({
                 "profile": profile,
                 "configured": configured,
+                "api_key_command": api_key_command,
                 "keyring_configured": keyring_configured,
                 "keyring_available": keyring_available,
+                "encrypted_file": encrypted_file,
                 "env_api_key": env_key.is_some(),
                 "env_profile": env_profile,
                 "validated": validated,
@@ -231,11 +288,18 @@ async fn status(validate: bool, output: &OutputOptions) -> Result<()> {
         profile.clone().unwrap_or_else(|| "none".to_string())
     );
     println!("Config file: {}", if configured { "yes" } else { "no" });
+    if let Some(command) = &api_key_command {
+        println!("Key command: {}", command);
+    }
     println!("Keyring: {}", if keyring_configured { "yes" } else { "no" });
     println!(
         "Keyring available: {}",
         if keyring_available { "yes" } else { "no" }
     );
+    println!(
+        "Encrypted key file: {}",
+        if encrypted_file { "yes" } else { "no" }
+    );
     println!(
         "Env API key override: {}",
         if env_key.is_some() { "yes" } else { "no" }
@@ -274,17 +338,39 @@ fn resolve_profile_for_write() -> Result<String> {
     Ok(config_data.current.unwrap_or_else(|| "default".to_string()))
 }
 
-#[cfg(feature = "secure-storage")]
-async fn migrate(keep_config: bool, force: bool, output: &OutputOptions) -> Result<()> {
-    if !crate::keyring::is_available() {
+async fn migrate(
+    to: MigrateTarget,
+    keep_config: bool,
+    force: bool,
+    output: &OutputOptions,
+) -> Result<()> {
+    let target = match to {
+        MigrateTarget::Keyring => "keyring",
+        MigrateTarget::File => "encrypted key file",
+    };
+    if to == MigrateTarget::Keyring {
+        #[cfg(not(feature = "secure-storage"))]
         anyhow::bail!(
-            "Keyring is not available on this system. Check that a secret service is running."
+            "The keyring requires the 'secure-storage' feature. Use --to file, or rebuild with: cargo build --features secure-storage"
         );
+        #[cfg(feature = "secure-storage")]
+        if !crate::keyring::is_available() {
+            anyhow::bail!(
+                "Keyring is not available on this system. Check that a secret service is running, or use --to file."
+            );
+        }
     }
 
     let config_data = config::load_config()?;
-
-    if config_data.workspaces.is_empty() {
+    // Profiles without a plaintext key (helpers, already migrated) have nothing to move
+    let workspace_names: Vec<String> = config_data
+        .workspaces
+        .iter()
+        .filter(|(_, w)| !w.api_key.is_empty())
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    if workspace_names.is_empty() {
         if output.is_json() || output.has_template() {
             print_json(
                 &json!({ "migrated": 0, "message": "No workspaces to migrate" }),
@@ -296,12 +382,11 @@ async fn migrate(keep_config: bool, force: bool, output: &OutputOptions) -> Resu
         return Ok(());
     }
 
-    let workspace_names: Vec<_> = config_data.workspaces.keys().cloned().collect();
-
     if !force {
         println!(
-            "This will migrate {} workspace(s) to the keyring:",
-            workspace_names.len()
+            "This will migrate {} workspace(s) to the {}:",
+            workspace_names.len(),
+            target
         );
         for name in &workspace_names {
             println!("  - {}", name);
@@ -319,18 +404,39 @@ async fn migrate(keep_config: bool, force: bool, output: &OutputOptions) -> Resu
     }
 
     let mut migrated = 0;
+    #[cfg_attr(not(feature = "secure-storage"), allow(unused_mut))]
     let mut failed: Vec<String> = Vec::new();
 
-    for (name, workspace) in &config_data.workspaces {
-        match crate::keyring::set_key(name, &workspace.api_key) {
-            Ok(()) => {
-                migrated += 1;
-                if !output.is_json() && !output.has_template() {
-                    println!("Migrated: {}", name);
+    match to {
+        MigrateTarget::Keyring =>
+        {
+            #[cfg(feature = "secure-storage")]
+            for name in &workspace_names {
+                match crate::keyring::set_key(name, &config_data.workspaces[name].api_key) {
+                    Ok(()) => {
+                        migrated += 1;
+                        if !output.is_json() && !output.has_template() {
+                            println!("Migrated: {}", name);
+                        }
+                    }
+                    Err(e) => {
+                        failed.push(format!("{}: {}", name, e));
+                    }
                 }
             }
-            Err(e) => {
-                failed.push(format!("{}: {}", name, e));
+        }
+        MigrateTarget::File => {
+            // One unlock for every profile; the file is written all at once
+            let entries: Vec<(String, String)> = workspace_names
+                .iter()
+                .map(|name| (name.clone(), config_data.workspaces[name].api_key.clone()))
+                .collect();
+            keystore::set_keys(&entries)?;
+            migrated = entries.len();
+            if !output.is_json() && !output.has_template() {
+                for name in &workspace_names {
+                    println!("Migrated: {}", name);
+                }
             }
         }
     }
@@ -350,6 +456,7 @@ async fn migrate(keep_config: bool, force: bool, output: &OutputOptions) -> Resu
         print_json(
             &json!({
                 "migrated": migrated,
+                "target": to.to_possible_value().map(|v| v.get_name().to_string()),
                 "failed": failed,
                 "config_cleared": !keep_config && failed.is_empty()
             }),
@@ -358,7 +465,7 @@ async fn migrate(keep_config: bool, force: bool, output: &OutputOptions) -> Resu
         return Ok(());
     }
 
-    println!("\nMigrated {} workspace(s) to keyring.", migrated);
+    println!("\nMigrated {} workspace(s) to the {}.", migrated, target);
     if !failed.is_empty() {
         println!("Failed to migrate:");
         for f in &failed {
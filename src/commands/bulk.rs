@@ -2,19 +2,25 @@ use anyhow::Result;
 use clap::Subcommand;
 use colored::Colorize;
 use futures::future::join_all;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::io::{self, BufRead};
 use std::path::Path;
 
-use crate::api::{resolve_cycle_id, resolve_project_id, resolve_team_id, LinearClient};
+use crate::api::{
+    ensure_client_id, resolve_cycle_id, resolve_project_id, resolve_team_id, LinearClient,
+};
 use crate::display_options;
 use crate::output::{print_json, OutputOptions};
+use crate::rate_limit;
+use crate::retry::{ErrorClass, IsRetryable};
 use crate::text::truncate;
 
 const BATCH_SIZE: usize = 50;
 
-#[derive(Subcommand)]
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum BulkCommands {
     /// Update the state of multiple issues
     #[command(alias = "state")]
@@ -326,6 +332,34 @@ pub async fn handle(cmd: BulkCommands, output: &OutputOptions) -> Result<()> {
     }
 }
 
+/// `update-state Done (3 issues)`: subcommand and arguments, for `outbox list`
+pub fn describe(cmd: &BulkCommands) -> String {
+    let (name, arg, issues) = match cmd {
+        BulkCommands::UpdateState { state, issues } => {
+            ("update-state", Some(state.clone()), issues)
+        }
+        BulkCommands::Assign { user, issues } => ("assign", Some(user.clone()), issues),
+        BulkCommands::Label { label, issues } => ("label", Some(label.clone()), issues),
+        BulkCommands::Unassign { issues } => ("unassign", None, issues),
+        BulkCommands::Priority { priority, issues } => {
+            ("priority", Some(priority.to_string()), issues)
+        }
+        BulkCommands::Project { project, issues } => ("project", Some(project.clone()), issues),
+        BulkCommands::Cycle { cycle, issues } => ("cycle", Some(cycle.clone()), issues),
+        BulkCommands::Archive { issues } => ("archive", None, issues),
+        BulkCommands::Create { data } => {
+            let count = parse_batch_create_issues(data)
+                .map(|i| i.len())
+                .unwrap_or(0);
+            return format!("create ({} issues)", count);
+        }
+    };
+    match arg {
+        Some(arg) => format!("{} {} ({} issues)", name, arg, issues.len()),
+        None => format!("{} ({} issues)", name, issues.len()),
+    }
+}
+
 fn ensure_issues_present(issues: &[String], output: &OutputOptions) -> Result<bool> {
     if !issues.is_empty() {
         return Ok(true);
@@ -342,15 +376,22 @@ fn ensure_issues_present(issues: &[String], output: &OutputOptions) -> Result<bo
     Ok(false)
 }
 
+/// Whether the API couldn't be reached at all. Such errors fail the whole
+/// command instead of being reported per issue, so the outbox can queue it.
+fn is_unreachable(err: &anyhow::Error) -> bool {
+    err.error_class() == ErrorClass::Network
+}
+
 async fn resolve_issue_infos(
     client: &LinearClient,
     issues: &[String],
-) -> (Vec<IssueInfo>, Vec<BulkResult>) {
+) -> Result<(Vec<IssueInfo>, Vec<BulkResult>)> {
     let futures: Vec<_> = issues
         .iter()
         .map(|issue_id| {
             let issue_id = issue_id.clone();
             async move {
+                rate_limit::throttle().await;
                 match get_issue_info(client, &issue_id).await {
                     Ok((uuid, team_id, identifier)) => Ok(IssueInfo {
                         issue_id,
@@ -358,12 +399,7 @@ async fn resolve_issue_infos(
                         team_id,
                         identifier,
                     }),
-                    Err(e) => Err(BulkResult {
-                        issue_id,
-                        success: false,
-                        identifier: None,
-                        error: Some(e.to_string()),
-                    }),
+                    Err(e) => Err((issue_id, e)),
                 }
             }
         })
@@ -374,10 +410,17 @@ async fn resolve_issue_infos(
     for result in join_all(futures).await {
         match result {
             Ok(info) => infos.push(info),
-            Err(failure) => failures.push(failure),
+            // Nothing has been written yet
+            Err((_, e)) if is_unreachable(&e) => return Err(e),
+            Err((issue_id, e)) => failures.push(BulkResult {
+                issue_id,
+                success: false,
+                identifier: None,
+                error: Some(e.to_string()),
+            }),
         }
     }
-    (infos, failures)
+    Ok((infos, failures))
 }
 
 async fn batch_update_infos(
@@ -400,6 +443,7 @@ async fn batch_update_infos(
     let mut results = Vec::new();
 
     for chunk in infos.chunks(BATCH_SIZE) {
+        rate_limit::throttle().await;
         let ids: Vec<String> = chunk.iter().map(|info| info.uuid.clone()).collect();
 
         match client
@@ -480,7 +524,7 @@ async fn bulk_update_state(state: &str, issues: Vec<String>, output: &OutputOpti
     }
 
     let client = LinearClient::new()?;
-    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await;
+    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await?;
 
     let mut grouped: HashMap<String, Vec<IssueInfo>> = HashMap::new();
     for info in infos {
@@ -530,6 +574,7 @@ async fn bulk_assign(user: &str, issues: Vec<String>, output: &OutputOptions) ->
     let client = LinearClient::new()?;
     let user_id = match resolve_user_id(&client, user).await {
         Ok(id) => id,
+        Err(e) if is_unreachable(&e) => return Err(e),
         Err(e) => {
             if output.is_json() || output.has_template() {
                 print_json(
@@ -543,7 +588,7 @@ async fn bulk_assign(user: &str, issues: Vec<String>, output: &OutputOptions) ->
         }
     };
 
-    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await;
+    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await?;
     let mut updated = batch_update_infos(&client, &infos, json!({ "assigneeId": user_id })).await;
     failures.append(&mut updated);
     print_summary(&failures, "assigned", output);
@@ -568,6 +613,7 @@ async fn bulk_label(label: &str, issues: Vec<String>, output: &OutputOptions) ->
 
     let label_id = match resolve_label_id(&client, label).await {
         Ok(id) => id,
+        Err(e) if is_unreachable(&e) => return Err(e),
         Err(e) => {
             if output.is_json() || output.has_template() {
                 print_json(
@@ -581,7 +627,7 @@ async fn bulk_label(label: &str, issues: Vec<String>, output: &OutputOptions) ->
         }
     };
 
-    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await;
+    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await?;
     let mut updated =
         batch_update_infos(&client, &infos, json!({ "addedLabelIds": [label_id] })).await;
     failures.append(&mut updated);
@@ -599,7 +645,7 @@ async fn bulk_unassign(issues: Vec<String>, output: &OutputOptions) -> Result<()
     }
 
     let client = LinearClient::new()?;
-    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await;
+    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await?;
     let mut updated = batch_update_infos(&client, &infos, json!({ "assigneeId": null })).await;
     failures.append(&mut updated);
     print_summary(&failures, "unassigned", output);
@@ -637,7 +683,7 @@ async fn bulk_update_priority(
     }
 
     let client = LinearClient::new()?;
-    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await;
+    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await?;
     let mut updated = batch_update_infos(&client, &infos, json!({ "priority": priority })).await;
     failures.append(&mut updated);
     print_summary(&failures, "priority updated", output);
@@ -666,6 +712,7 @@ async fn bulk_move_project(
     let client = LinearClient::new()?;
     let project_id = match resolve_project_id(&client, project, true).await {
         Ok(id) => id,
+        Err(e) if is_unreachable(&e) => return Err(e),
         Err(e) => {
             if output.is_json() || output.has_template() {
                 print_json(
@@ -684,7 +731,7 @@ async fn bulk_move_project(
         }
     };
 
-    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await;
+    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await?;
     let mut updated = batch_update_infos(&client, &infos, json!({ "projectId": project_id })).await;
     failures.append(&mut updated);
     print_summary(&failures, "moved to project", output);
@@ -707,7 +754,7 @@ async fn bulk_move_cycle(cycle: &str, issues: Vec<String>, output: &OutputOption
     }
 
     let client = LinearClient::new()?;
-    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await;
+    let (infos, mut failures) = resolve_issue_infos(&client, &issues).await?;
 
     let mut grouped: HashMap<String, Vec<IssueInfo>> = HashMap::new();
     for info in infos {
@@ -755,7 +802,10 @@ async fn bulk_archive(issues: Vec<String>, output: &OutputOptions) -> Result<()>
         .map(|issue_id| {
             let client = &client;
             let id = issue_id.clone();
-            async move { archive_issue(client, &id).await }
+            async move {
+                rate_limit::throttle().await;
+                archive_issue(client, &id).await
+            }
         })
         .collect();
 
@@ -804,8 +854,34 @@ async fn bulk_create(data: &str, output: &OutputOptions) -> Result<()> {
         }
     "#;
 
+    // Client-assigned IDs let a retry detect that an earlier attempt landed
+    let ids: Vec<String> = issues.iter_mut().map(ensure_client_id).collect();
+    let lookup = r#"
+        query($ids: [ID!]) {
+            issues(first: 250, filter: { id: { in: $ids } }) {
+                nodes {
+                    id
+                    identifier
+                    title
+                }
+            }
+        }
+    "#;
+
     let result = client
-        .mutate(mutation, Some(json!({ "input": { "issues": issues } })))
+        .mutate_with_check(
+            mutation,
+            json!({ "input": { "issues": issues } }),
+            lookup,
+            json!({ "ids": ids }),
+            |existing| {
+                let found = existing["data"]["issues"]["nodes"].as_array()?;
+                // The batch is all-or-nothing, so any match means it went through
+                (!found.is_empty()).then(|| {
+                    json!({ "data": { "issueBatchCreate": { "success": true, "issues": found } } })
+                })
+            },
+        )
         .await?;
 
     if result["data"]["issueBatchCreate"]["success"].as_bool() != Some(true) {
@@ -850,7 +926,7 @@ async fn bulk_create(data: &str, output: &OutputOptions) -> Result<()> {
     Ok(())
 }
 
-fn parse_batch_create_issues(data: &str) -> Result<Vec<Value>> {
+pub fn parse_batch_create_issues(data: &str) -> Result<Vec<Value>> {
     let raw = if data == "-" {
         let stdin = io::stdin();
         let lines: Vec<String> = stdin.lock().lines().map_while(Result::ok).collect();
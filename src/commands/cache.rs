@@ -1,20 +1,45 @@
 use anyhow::Result;
 use clap::Subcommand;
 use colored::Colorize;
+use futures::future::join_all;
+use serde_json::{json, Map, Value};
+use std::process::{Child, Command, Stdio};
+use std::time::Instant;
 use tabled::{Table, Tabled};
 
-use crate::cache::{Cache, CacheType};
+use crate::api::LinearClient;
+use crate::cache::{self, Cache, CacheType};
+use crate::config;
+use crate::error::CliError;
+use crate::output::{print_json, OutputOptions};
+use crate::pagination::{paginate_nodes, PaginationOptions};
 
 #[derive(Subcommand)]
 pub enum CacheCommands {
     /// Clear all cached data
     Clear {
-        /// Only clear a specific cache type (teams, users, statuses, labels)
+        /// Only clear a specific cache type (teams, users, statuses, labels, projects,
+        /// issue-ids, cycle-ids, project-ids)
         #[arg(short, long)]
         r#type: Option<String>,
     },
-    /// Show cache status and sizes
+    /// Show cache status, sizes and entry counts
     Status,
+    /// Prune expired entries, trim keyed caches to the size cap and delete
+    /// caches of removed profiles
+    Gc,
+    /// Prefetch cached data so name lookups don't have to page through the API
+    Refresh {
+        /// Only refresh these types (comma-separated: teams, users, statuses, labels, projects)
+        #[arg(short, long, value_delimiter = ',')]
+        r#type: Vec<String>,
+        /// Skip entries that are still fresh
+        #[arg(long)]
+        stale: bool,
+        /// Refresh stale entries in a detached process and return immediately
+        #[arg(long)]
+        background: bool,
+    },
 }
 
 #[derive(Tabled)]
@@ -31,29 +56,79 @@ struct CacheStatusRow {
     items: String,
 }
 
-pub async fn handle(cmd: CacheCommands) -> Result<()> {
+pub async fn handle(cmd: CacheCommands, output: &OutputOptions) -> Result<()> {
     match cmd {
         CacheCommands::Clear { r#type } => clear_cache(r#type).await,
-        CacheCommands::Status => show_status().await,
+        CacheCommands::Status => show_status(output).await,
+        CacheCommands::Gc => gc(output),
+        CacheCommands::Refresh {
+            r#type,
+            stale,
+            background,
+        } => {
+            let types = parse_cache_types(&r#type)?;
+            if background {
+                spawn_background_refresh(&r#type, output)
+            } else {
+                refresh(&types, stale, output).await
+            }
+        }
     }
 }
 
+fn parse_cache_type(name: &str) -> Result<CacheType> {
+    Ok(match name.trim().to_lowercase().as_str() {
+        "teams" => CacheType::Teams,
+        "users" => CacheType::Users,
+        "statuses" | "states" => CacheType::Statuses,
+        "labels" => CacheType::Labels,
+        "projects" => CacheType::Projects,
+        "issue-ids" => CacheType::IssueIds,
+        "cycle-ids" => CacheType::CycleIds,
+        "project-ids" => CacheType::ProjectIds,
+        _ => {
+            return Err(CliError::new(
+                5,
+                format!(
+                    "Unknown cache type: '{}'. Valid types: teams, users, statuses, labels, \
+                     projects, issue-ids, cycle-ids, project-ids",
+                    name
+                ),
+            )
+            .into());
+        }
+    })
+}
+
+/// The named types, or every prefetchable type if none are given
+fn parse_cache_types(names: &[String]) -> Result<Vec<CacheType>> {
+    if names.is_empty() {
+        return Ok(CacheType::prefetchable().to_vec());
+    }
+    names
+        .iter()
+        .map(|name| {
+            let cache_type = parse_cache_type(name)?;
+            if !CacheType::prefetchable().contains(&cache_type) {
+                return Err(CliError::new(
+                    5,
+                    format!(
+                        "{} can't be prefetched; it fills in as IDs are resolved",
+                        cache_type.display_name()
+                    ),
+                )
+                .into());
+            }
+            Ok(cache_type)
+        })
+        .collect()
+}
+
 async fn clear_cache(cache_type: Option<String>) -> Result<()> {
     let cache = Cache::new()?;
 
     if let Some(type_str) = cache_type {
-        let cache_type = match type_str.to_lowercase().as_str() {
-            "teams" => CacheType::Teams,
-            "users" => CacheType::Users,
-            "statuses" | "states" => CacheType::Statuses,
-            "labels" => CacheType::Labels,
-            _ => {
-                anyhow::bail!(
-                    "Unknown cache type: '{}'. Valid types: teams, users, statuses, labels",
-                    type_str
-                );
-            }
-        };
+        let cache_type = parse_cache_type(&type_str)?;
         cache.clear_type(cache_type)?;
         println!(
             "{} Cleared {} cache",
@@ -68,9 +143,30 @@ async fn clear_cache(cache_type: Option<String>) -> Result<()> {
     Ok(())
 }
 
-async fn show_status() -> Result<()> {
+async fn show_status(output: &OutputOptions) -> Result<()> {
     let cache = Cache::new()?;
     let statuses = cache.status();
+    let max_entries = config::cache_max_entries()?;
+
+    if output.is_json() || output.has_template() {
+        let types: Vec<Value> = statuses
+            .iter()
+            .map(|s| {
+                json!({
+                    "type": s.cache_type.display_name(),
+                    "valid": s.valid,
+                    "age_seconds": s.age_seconds,
+                    "size_bytes": s.size_bytes,
+                    "items": s.item_count,
+                })
+            })
+            .collect();
+        print_json(
+            &json!({ "types": types, "max_entries": max_entries }),
+            output,
+        )?;
+        return Ok(());
+    }
 
     println!("{}", "Cache Status".bold());
     println!("{}", "-".repeat(50));
@@ -112,6 +208,276 @@ async fn show_status() -> Result<()> {
         };
         println!("Total cache size: {}", size_display);
     }
+    println!("Keyed caches hold at most {} entries each", max_entries);
+
+    Ok(())
+}
+
+fn gc(output: &OutputOptions) -> Result<()> {
+    let report = cache::gc()?;
+
+    if output.is_json() || output.has_template() {
+        print_json(&serde_json::to_value(&report)?, output)?;
+        return Ok(());
+    }
+
+    println!(
+        "{} Removed {} expired entries, evicted {} over the cap, deleted {} stale temp files",
+        "+".green(),
+        report.expired,
+        report.evicted,
+        report.temp_files
+    );
+    for profile in &report.orphaned_profiles {
+        println!(
+            "{} Deleted cache of removed profile '{}'",
+            "+".green(),
+            profile
+        );
+    }
+    for profile in &report.kept_with_outbox {
+        println!(
+            "{} Kept cache of removed profile '{}': its outbox still has queued writes",
+            "!".yellow(),
+            profile
+        );
+    }
+    Ok(())
+}
+
+/// Fetch everything a cache type holds, in the format the resolvers read
+async fn fetch(client: &LinearClient, cache_type: CacheType) -> Result<Value> {
+    let (query, field, page_size) = match cache_type {
+        CacheType::Teams => (
+            r#"
+            query($first: Int, $after: String) {
+                teams(first: $first, after: $after) {
+                    nodes { id key name }
+                    pageInfo { hasNextPage endCursor }
+                }
+            }
+            "#,
+            "teams",
+            250,
+        ),
+        CacheType::Users => (
+            r#"
+            query($first: Int, $after: String) {
+                users(first: $first, after: $after) {
+                    nodes { id name email }
+                    pageInfo { hasNextPage endCursor }
+                }
+            }
+            "#,
+            "users",
+            250,
+        ),
+        CacheType::Labels => (
+            r#"
+            query($first: Int, $after: String) {
+                issueLabels(first: $first, after: $after) {
+                    nodes { id name team { id } }
+                    pageInfo { hasNextPage endCursor }
+                }
+            }
+            "#,
+            "issueLabels",
+            250,
+        ),
+        CacheType::Projects => (
+            r#"
+            query($first: Int, $after: String) {
+                projects(first: $first, after: $after) {
+                    nodes { id name url }
+                    pageInfo { hasNextPage endCursor }
+                }
+            }
+            "#,
+            "projects",
+            250,
+        ),
+        CacheType::Statuses => (
+            r#"
+            query($first: Int, $after: String) {
+                teams(first: $first, after: $after) {
+                    nodes {
+                        id
+                        name
+                        states(first: 250) {
+                            nodes { id name type color position description }
+                        }
+                    }
+                    pageInfo { hasNextPage endCursor }
+                }
+            }
+            "#,
+            "teams",
+            50,
+        ),
+        CacheType::IssueIds | CacheType::CycleIds | CacheType::ProjectIds => {
+            anyhow::bail!("{} can't be prefetched", cache_type.display_name())
+        }
+    };
 
+    let pagination = PaginationOptions {
+        all: true,
+        page_size: Some(page_size),
+        ..Default::default()
+    };
+    let nodes = paginate_nodes(
+        client,
+        query,
+        Map::new(),
+        &["data", field, "nodes"],
+        &["data", field, "pageInfo"],
+        &pagination,
+        page_size,
+    )
+    .await?;
+
+    if cache_type != CacheType::Statuses {
+        return Ok(Value::Array(nodes));
+    }
+    // Statuses are keyed by team ID, as `statuses list` stores them
+    let by_team: Map<String, Value> = nodes
+        .iter()
+        .filter_map(|team| {
+            let id = team["id"].as_str()?;
+            Some((
+                id.to_string(),
+                json!({
+                    "team_name": team["name"],
+                    "states": team["states"]["nodes"],
+                }),
+            ))
+        })
+        .collect();
+    Ok(Value::Object(by_team))
+}
+
+fn is_stale(cache: &Cache, cache_type: CacheType) -> bool {
+    cache
+        .get_entry(cache_type)
+        .is_none_or(|entry| !entry.is_valid())
+}
+
+async fn refresh(types: &[CacheType], stale_only: bool, output: &OutputOptions) -> Result<()> {
+    let client = LinearClient::new()?;
+    let cache = Cache::with_ttl(output.cache.effective_ttl_seconds())?;
+    let types: Vec<CacheType> = types
+        .iter()
+        .copied()
+        .filter(|t| !stale_only || is_stale(&cache, *t))
+        .collect();
+
+    let results = join_all(types.iter().map(|&cache_type| {
+        let client = &client;
+        let cache = &cache;
+        async move {
+            let started = Instant::now();
+            let result = match fetch(client, cache_type).await {
+                Ok(data) => {
+                    let items = data
+                        .as_array()
+                        .map(|a| a.len())
+                        .or_else(|| data.as_object().map(|o| o.len()))
+                        .unwrap_or(0);
+                    cache.set(cache_type, data).map(|()| items)
+                }
+                Err(e) => Err(e),
+            };
+            (cache_type, result, started.elapsed())
+        }
+    }))
+    .await;
+
+    let failed = results.iter().filter(|(_, r, _)| r.is_err()).count();
+    if output.is_json() || output.has_template() {
+        let rows: Vec<Value> = results
+            .iter()
+            .map(|(cache_type, result, elapsed)| {
+                json!({
+                    "type": cache_type.display_name().to_lowercase(),
+                    "items": result.as_ref().ok(),
+                    "error": result.as_ref().err().map(|e| e.to_string()),
+                    "elapsed_ms": elapsed.as_millis() as u64,
+                })
+            })
+            .collect();
+        print_json(&json!(rows), output)?;
+    } else if results.is_empty() {
+        println!("All caches are fresh.");
+    } else {
+        for (cache_type, result, elapsed) in &results {
+            match result {
+                Ok(items) => println!(
+                    "{} {}: {} items ({:.1}s)",
+                    "+".green(),
+                    cache_type.display_name(),
+                    items,
+                    elapsed.as_secs_f64()
+                ),
+                Err(e) => println!("{} {}: {}", "x".red(), cache_type.display_name(), e),
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of {} cache types failed to refresh",
+            failed,
+            results.len()
+        );
+    }
+    Ok(())
+}
+
+/// Re-run `cache refresh --stale` detached, so the caller doesn't wait
+fn spawn_refresh(types: &[String], output: &OutputOptions) -> Result<Child> {
+    let mut command = Command::new(std::env::current_exe()?);
+    command.args(["cache", "refresh", "--stale", "--quiet"]);
+    if !types.is_empty() {
+        command.args(["--type", &types.join(",")]);
+    }
+    if let Some(ttl) = output.cache.ttl_seconds {
+        command.args(["--cache-ttl", &ttl.to_string()]);
+    }
+    Ok(command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?)
+}
+
+/// After a command, refresh in the background the caches its name lookups
+/// found expired, so the next command resolves from the cache instead of
+/// querying the API. Best-effort and silent.
+pub fn refresh_expired_in_background(output: &OutputOptions) {
+    let Ok(cache) = Cache::new() else {
+        return;
+    };
+    // The lookup's own fallback may have refilled the cache already
+    let types: Vec<String> = cache::expired_types()
+        .into_iter()
+        .filter(|t| is_stale(&cache, *t))
+        .map(|t| t.display_name().to_lowercase())
+        .collect();
+    if !types.is_empty() {
+        let _ = spawn_refresh(&types, output);
+    }
+}
+
+fn spawn_background_refresh(types: &[String], output: &OutputOptions) -> Result<()> {
+    let child = spawn_refresh(types, output)?;
+
+    if output.is_json() || output.has_template() {
+        print_json(&json!({ "background": true, "pid": child.id() }), output)?;
+    } else {
+        println!(
+            "{} Refreshing stale caches in the background (pid {})",
+            "+".green(),
+            child.id()
+        );
+    }
     Ok(())
 }
@@ -8,8 +8,11 @@ use crate::api::LinearClient;
 use crate::display_options;
 use crate::input::read_ids_from_stdin;
 use crate::output::{ensure_non_empty, filter_values, print_json, sort_values, OutputOptions};
-use crate::pagination::paginate_nodes;
+use crate::queries::{self, Fetched};
 use crate::text::truncate;
+use crate::AgentOptions;
+
+use super::outbox::{self, Operation};
 
 #[derive(Subcommand)]
 pub enum CommentCommands {
@@ -73,14 +76,26 @@ struct CommentRow {
     id: String,
 }
 
-pub async fn handle(cmd: CommentCommands, output: &OutputOptions) -> Result<()> {
+pub async fn handle(
+    cmd: CommentCommands,
+    output: &OutputOptions,
+    agent_opts: AgentOptions,
+) -> Result<()> {
     match cmd {
         CommentCommands::List { issue_ids } => list_comments(&issue_ids, output).await,
         CommentCommands::Create {
             issue_id,
             body,
             parent_id,
-        } => create_comment(&issue_id, &body, parent_id, output).await,
+        } => {
+            let operation = Operation::CommentCreate(outbox::CommentCreate {
+                issue_id,
+                body,
+                parent_id,
+                id: None,
+            });
+            outbox::submit(operation, output, agent_opts).await
+        }
         CommentCommands::Update {
             id,
             body,
@@ -106,20 +121,30 @@ async fn list_comments(issue_ids: &[String], output: &OutputOptions) -> Result<(
     let pagination = output.pagination.with_default_limit(100);
     let mut issues = Vec::new();
     for id in &final_ids {
-        let mut issue = fetch_issue_meta(&client, id).await?;
-        if issue.is_null() {
-            if !output.is_json() && !output.has_template() {
-                eprintln!("{} Issue not found: {}", "!".yellow(), id);
+        let issue = match queries::get_issue_ref(&client, id).await {
+            Ok(issue) => issue,
+            Err(e) if queries::is_not_found(&e) => {
+                if !output.is_json() && !output.has_template() {
+                    eprintln!("{} Issue not found: {}", "!".yellow(), id);
+                }
+                continue;
             }
-            continue;
-        }
-        let comments = fetch_issue_comments(&client, id, &pagination).await?;
-        issue["comments"] = json!({ "nodes": comments });
-        issues.push(issue);
+            Err(e) => return Err(e),
+        };
+        let comments = queries::list_comments(&client, id, &pagination).await?;
+        issues.push((issue, comments));
     }
 
     // JSON output - return raw data for LLM consumption
     if output.is_json() || output.has_template() {
+        let issues: Vec<serde_json::Value> = issues
+            .iter()
+            .map(|(issue, comments)| {
+                let mut raw = issue.raw.clone();
+                raw["comments"] = json!({ "nodes": queries::raw_values(comments) });
+                raw
+            })
+            .collect();
         if issues.len() == 1 {
             print_json(&issues[0], output)?;
         } else {
@@ -128,21 +153,16 @@ async fn list_comments(issue_ids: &[String], output: &OutputOptions) -> Result<(
         return Ok(());
     }
 
-    for (idx, issue) in issues.iter().enumerate() {
+    for (idx, (issue, mut comments)) in issues.into_iter().enumerate() {
         if idx > 0 {
             println!();
         }
-        let identifier = issue["identifier"].as_str().unwrap_or("");
-        let title = issue["title"].as_str().unwrap_or("");
+        let issue = issue.data;
+        let title = issue.title.as_deref().unwrap_or("");
 
-        println!("{} {}", identifier.bold(), title);
+        println!("{} {}", issue.identifier.bold(), title);
         println!("{}", "-".repeat(50));
 
-        let mut comments = issue["comments"]["nodes"]
-            .as_array()
-            .cloned()
-            .unwrap_or_default();
-
         filter_values(&mut comments, &output.filters);
 
         if let Some(sort_key) = output.json.sort.as_deref() {
@@ -158,12 +178,13 @@ async fn list_comments(issue_ids: &[String], output: &OutputOptions) -> Result<(
         let width = display_options().max_width(60);
         let rows: Vec<CommentRow> = comments
             .iter()
-            .map(|c| {
-                let body = c["body"].as_str().unwrap_or("");
+            .map(|Fetched { data: c, .. }| {
+                let body = c.body.as_deref().unwrap_or("");
                 let truncated_body = truncate(body, width);
 
-                let created_at = c["createdAt"]
-                    .as_str()
+                let created_at = c
+                    .created_at
+                    .as_deref()
                     .unwrap_or("")
                     .split('T')
                     .next()
@@ -171,10 +192,14 @@ async fn list_comments(issue_ids: &[String], output: &OutputOptions) -> Result<(
                     .to_string();
 
                 CommentRow {
-                    author: c["user"]["name"].as_str().unwrap_or("Unknown").to_string(),
+                    author: c
+                        .user
+                        .as_ref()
+                        .map(|u| u.name.clone())
+                        .unwrap_or_else(|| "Unknown".to_string()),
                     created_at,
                     body: truncated_body.replace('\n', " "),
-                    id: c["id"].as_str().unwrap_or("").to_string(),
+                    id: c.id.clone(),
                 }
             })
             .collect();
@@ -187,69 +212,11 @@ async fn list_comments(issue_ids: &[String], output: &OutputOptions) -> Result<(
     Ok(())
 }
 
-async fn fetch_issue_meta(client: &LinearClient, issue_id: &str) -> Result<serde_json::Value> {
-    let query = r#"
-        query($issueId: String!) {
-            issue(id: $issueId) {
-                id
-                identifier
-                title
-            }
-        }
-    "#;
-
-    let result = client
-        .query(query, Some(json!({ "issueId": issue_id })))
-        .await?;
-    Ok(result["data"]["issue"].clone())
-}
-
-async fn fetch_issue_comments(
-    client: &LinearClient,
-    issue_id: &str,
-    pagination: &crate::pagination::PaginationOptions,
-) -> Result<Vec<serde_json::Value>> {
-    let query = r#"
-        query($issueId: String!, $first: Int, $after: String, $last: Int, $before: String) {
-            issue(id: $issueId) {
-                comments(first: $first, after: $after, last: $last, before: $before) {
-                    nodes {
-                        id
-                        body
-                        createdAt
-                        user { name email }
-                        parent { id }
-                    }
-                    pageInfo {
-                        hasNextPage
-                        endCursor
-                        hasPreviousPage
-                        startCursor
-                    }
-                }
-            }
-        }
-    "#;
-
-    let mut vars = serde_json::Map::new();
-    vars.insert("issueId".to_string(), json!(issue_id));
-
-    paginate_nodes(
-        client,
-        query,
-        vars,
-        &["data", "issue", "comments", "nodes"],
-        &["data", "issue", "comments", "pageInfo"],
-        pagination,
-        100,
-    )
-    .await
-}
-
-async fn create_comment(
+pub async fn create_comment(
     issue_id: &str,
     body: &str,
     parent_id: Option<String>,
+    client_id: Option<String>,
     output: &OutputOptions,
 ) -> Result<()> {
     let client = LinearClient::new()?;
@@ -258,6 +225,9 @@ async fn create_comment(
         "issueId": issue_id,
         "body": body
     });
+    if let Some(id) = client_id {
+        input["id"] = json!(id);
+    }
 
     if let Some(pid) = parent_id {
         input["parentId"] = json!(pid);
@@ -277,9 +247,20 @@ async fn create_comment(
             }
         }
     "#;
+    let lookup = r#"
+        query($id: String!) {
+            comment(id: $id) {
+                id
+                body
+                createdAt
+                user { name }
+                issue { identifier title }
+            }
+        }
+    "#;
 
     let result = client
-        .mutate(mutation, Some(json!({ "input": input })))
+        .create_entity(mutation, input, "commentCreate", "comment", lookup)
         .await?;
 
     if result["data"]["commentCreate"]["success"].as_bool() == Some(true) {
@@ -9,6 +9,7 @@ use tabled::{Table, Tabled};
 use crate::api::{
     resolve_initiative_id, resolve_project_id, resolve_team_id, resolve_user_id, LinearClient,
 };
+use crate::batch::{self, BatchQuery};
 use crate::display_options;
 use crate::input::read_ids_from_stdin;
 use crate::output::{ensure_non_empty, filter_values, print_json, sort_values, OutputOptions};
@@ -323,58 +324,42 @@ async fn list_custom_views(include_archived: bool, output: &OutputOptions) -> Re
     Ok(())
 }
 
+const CUSTOM_VIEW_DETAIL: &str = r#"
+    fragment CustomViewDetail on CustomView {
+        id
+        name
+        description
+        icon
+        color
+        shared
+        slugId
+        createdAt
+        updatedAt
+        archivedAt
+        owner { id name email }
+        team { id key name }
+        filterData
+        projectFilterData
+        initiativeFilterData
+        feedItemFilterData
+    }
+"#;
+
+const CUSTOM_VIEW_BATCH: BatchQuery = BatchQuery {
+    field: "customView",
+    fragment_name: "CustomViewDetail",
+    fragment: CUSTOM_VIEW_DETAIL,
+    what: "Custom view",
+};
+
 async fn get_custom_views(ids: &[String], output: &OutputOptions) -> Result<()> {
     if ids.len() == 1 {
         return get_custom_view(&ids[0], output).await;
     }
 
     let client = LinearClient::new()?;
-    let futures: Vec<_> = ids
-        .iter()
-        .map(|id| {
-            let client = client.clone();
-            let id = id.clone();
-            async move {
-                let query = r#"
-                    query($id: String!) {
-                        customView(id: $id) {
-                            id
-                            name
-                            description
-                            icon
-                            color
-                            shared
-                            slugId
-                            createdAt
-                            updatedAt
-                            archivedAt
-                            owner { id name email }
-                            team { id key name }
-                            filterData
-                            projectFilterData
-                            initiativeFilterData
-                            feedItemFilterData
-                        }
-                    }
-                "#;
-
-                match client.query(query, Some(json!({ "id": id.clone() }))).await {
-                    Ok(result) => {
-                        let view = result["data"]["customView"].clone();
-                        if view.is_null() {
-                            (id, Err(anyhow::anyhow!("Custom view not found")))
-                        } else {
-                            (id, Ok(view))
-                        }
-                    }
-                    Err(e) => (id, Err(e)),
-                }
-            }
-        })
-        .collect();
-
     let mut results = Vec::new();
-    for (id, result) in futures::future::join_all(futures).await {
+    for (id, result) in batch::fetch_by_ids(&client, &CUSTOM_VIEW_BATCH, ids).await? {
         match result {
             Ok(view) => results.push(view),
             Err(e) => {
@@ -403,42 +388,17 @@ async fn get_custom_views(ids: &[String], output: &OutputOptions) -> Result<()>
 
 async fn get_custom_view(id: &str, output: &OutputOptions) -> Result<()> {
     let client = LinearClient::new()?;
-    let query = r#"
-        query($id: String!) {
-            customView(id: $id) {
-                id
-                name
-                description
-                icon
-                color
-                shared
-                slugId
-                createdAt
-                updatedAt
-                archivedAt
-                owner { id name email }
-                team { id key name }
-                filterData
-                projectFilterData
-                initiativeFilterData
-                feedItemFilterData
-            }
-        }
-    "#;
-
-    let result = client.query(query, Some(json!({ "id": id }))).await?;
-    let view = &result["data"]["customView"];
-
-    if view.is_null() {
-        anyhow::bail!("Custom view not found: {}", id);
-    }
+    let (_, result) = batch::fetch_by_ids(&client, &CUSTOM_VIEW_BATCH, &[id.to_string()])
+        .await?
+        .remove(0);
+    let view = result?;
 
     if output.is_json() || output.has_template() {
-        print_json(view, output)?;
+        print_json(&view, output)?;
         return Ok(());
     }
 
-    print_custom_view_details(view);
+    print_custom_view_details(&view);
     Ok(())
 }
 
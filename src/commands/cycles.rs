@@ -8,8 +8,9 @@ use tabled::{Table, Tabled};
 use crate::api::{resolve_team_id, LinearClient};
 use crate::display_options;
 use crate::output::{ensure_non_empty, filter_values, print_json, sort_values, OutputOptions};
-use crate::pagination::paginate_nodes;
+use crate::queries::{self, Fetched};
 use crate::text::truncate;
+use crate::types::Cycle;
 
 #[derive(Subcommand)]
 pub enum CycleCommands {
@@ -104,74 +105,21 @@ async fn list_cycles(team: &str, include_all: bool, output: &OutputOptions) -> R
     // Resolve team key/name to UUID
     let team_id = resolve_team_id(&client, team, &output.cache).await?;
 
-    let team_query = r#"
-        query($teamId: String!) {
-            team(id: $teamId) {
-                id
-                name
-            }
-        }
-    "#;
-
-    let result = client
-        .query(team_query, Some(json!({ "teamId": team_id })))
-        .await?;
-    let team_data = &result["data"]["team"];
-
-    if team_data.is_null() {
-        anyhow::bail!("Team not found: {}", team);
-    }
-
-    let team_name = team_data["name"].as_str().unwrap_or("");
+    let team_data = queries::get_team(&client, &team_id).await?.data;
+    let team_name = team_data.name.as_str();
 
-    let cycles_query = r#"
-        query($teamId: String!, $first: Int, $after: String, $last: Int, $before: String) {
-            team(id: $teamId) {
-                cycles(first: $first, after: $after, last: $last, before: $before) {
-                    nodes {
-                        id
-                        name
-                        number
-                        startsAt
-                        endsAt
-                        completedAt
-                        progress
-                    }
-                    pageInfo {
-                        hasNextPage
-                        endCursor
-                        hasPreviousPage
-                        startCursor
-                    }
-                }
-            }
-        }
-    "#;
-
-    let mut vars = serde_json::Map::new();
-    vars.insert("teamId".to_string(), json!(team_id));
     let pagination = output.pagination.with_default_limit(50);
-    let cycles = paginate_nodes(
-        &client,
-        cycles_query,
-        vars,
-        &["data", "team", "cycles", "nodes"],
-        &["data", "team", "cycles", "pageInfo"],
-        &pagination,
-        50,
-    )
-    .await?;
-
-    let cycles: Vec<_> = cycles
+    let cycles: Vec<Fetched<Cycle>> = queries::list_cycles(&client, &team_id, &pagination)
+        .await?
         .into_iter()
-        .filter(|c| include_all || c["completedAt"].is_null())
+        .filter(|c| include_all || c.data.completed_at.is_none())
         .collect();
 
     if output.is_json() || output.has_template() {
         print_json(
             &json!({
                 "team": team_name,
-                "cycles": cycles
+                "cycles": queries::raw_values(&cycles)
             }),
             output,
         )?;
@@ -193,32 +141,31 @@ async fn list_cycles(team: &str, include_all: bool, output: &OutputOptions) -> R
     let width = display_options().max_width(30);
     let rows: Vec<CycleRow> = filtered
         .iter()
-        .map(|c| {
-            let progress = c["progress"].as_f64().unwrap_or(0.0);
+        .map(|Fetched { data: c, .. }| {
+            let progress = c.progress.unwrap_or(0.0);
 
-            let status = if !c["completedAt"].is_null() {
+            let status = if c.completed_at.is_some() {
                 "Completed".to_string()
             } else {
                 "Active".to_string()
             };
 
             CycleRow {
-                name: truncate(c["name"].as_str().unwrap_or("-"), width),
-                number: c["number"]
-                    .as_i64()
-                    .map(|n| n.to_string())
-                    .unwrap_or("-".to_string()),
+                name: truncate(c.name.as_deref().unwrap_or("-"), width),
+                number: c.number.map(|n| n.to_string()).unwrap_or("-".to_string()),
                 status,
-                start_date: c["startsAt"]
-                    .as_str()
+                start_date: c
+                    .starts_at
+                    .as_deref()
                     .map(|s| s.chars().take(10).collect())
                     .unwrap_or("-".to_string()),
-                end_date: c["endsAt"]
-                    .as_str()
+                end_date: c
+                    .ends_at
+                    .as_deref()
                     .map(|s| s.chars().take(10).collect())
                     .unwrap_or("-".to_string()),
                 progress: format!("{:.0}%", progress * 100.0),
-                id: c["id"].as_str().unwrap_or("").to_string(),
+                id: c.id.clone(),
             }
         })
         .collect();
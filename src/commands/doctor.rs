@@ -5,6 +5,7 @@ use crate::api::LinearClient;
 use crate::cache;
 use crate::config;
 use crate::output::{print_json, OutputOptions};
+use crate::rate_limit::{self, RateLimitBudget};
 
 pub async fn run(output: &OutputOptions, check_api: bool) -> Result<()> {
     let config_path = config::config_file_path()?;
@@ -36,6 +37,9 @@ pub async fn run(output: &OutputOptions, check_api: bool) -> Result<()> {
         }
     }
 
+    // Read after the API check so it reflects that response
+    let budget = rate_limit::current();
+
     if output.is_json() || output.has_template() {
         print_json(
             &json!({
@@ -48,6 +52,7 @@ pub async fn run(output: &OutputOptions, check_api: bool) -> Result<()> {
                 "cache_ttl_seconds": output.cache.effective_ttl_seconds(),
                 "api_ok": api_ok,
                 "api_error": api_error,
+                "rate_limit": budget,
             }),
             output,
         )?;
@@ -67,6 +72,10 @@ pub async fn run(output: &OutputOptions, check_api: bool) -> Result<()> {
     );
     println!("Cache dir: {}", cache_dir.display());
     println!("Cache TTL: {}s", output.cache.effective_ttl_seconds());
+    match &budget {
+        Some(budget) => println!("Rate limit: {}", format_budget(budget)),
+        None => println!("Rate limit: unknown (no API response seen yet)"),
+    }
     if let Some(api_ok) = api_ok {
         println!("API check: {}", if api_ok { "ok" } else { "failed" });
         if let Some(err) = api_error {
@@ -93,3 +102,37 @@ async fn validate_api() -> Result<()> {
     }
     Ok(())
 }
+
+fn format_budget(budget: &RateLimitBudget) -> String {
+    let part = |remaining: Option<u64>, limit: Option<u64>, reset: Option<u64>, what: &str| {
+        let remaining = remaining?;
+        let mut text = match limit {
+            Some(limit) => format!("{}/{} {}", remaining, limit, what),
+            None => format!("{} {}", remaining, what),
+        };
+        if let Some(reset) = reset.and_then(|ms| chrono::DateTime::from_timestamp_millis(ms as i64))
+        {
+            let local = reset.with_timezone(&chrono::Local);
+            text.push_str(&format!(" (resets {})", local.format("%H:%M:%S")));
+        }
+        Some(text)
+    };
+    [
+        part(
+            budget.requests_remaining,
+            budget.requests_limit,
+            budget.requests_reset,
+            "requests",
+        ),
+        part(
+            budget.complexity_remaining,
+            budget.complexity_limit,
+            budget.complexity_reset,
+            "complexity",
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(", ")
+}
@@ -490,9 +490,14 @@ async fn create_document(
             }
         }
     "#;
+    let lookup = r#"
+        query($id: String!) {
+            document(id: $id) { id title url }
+        }
+    "#;
 
     let result = client
-        .mutate(mutation, Some(json!({ "input": input })))
+        .create_entity(mutation, input, "documentCreate", "document", lookup)
         .await?;
 
     if result["data"]["documentCreate"]["success"].as_bool() == Some(true) {
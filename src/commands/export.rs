@@ -1,10 +1,11 @@
 use anyhow::Result;
 use clap::Subcommand;
 use csv::Writer;
-use serde_json::json;
+use serde_json::{json, Value};
 use std::io::Write;
 
 use crate::api::LinearClient;
+use crate::mirror::Mirror;
 use crate::output::OutputOptions;
 use crate::pagination::{paginate_nodes, stream_nodes, PaginationOptions};
 
@@ -27,6 +28,9 @@ pub enum ExportCommands {
         /// Export all matching issues
         #[arg(long)]
         all: bool,
+        /// Export from the local mirror instead of the API (see: linear mirror)
+        #[arg(long)]
+        local: bool,
     },
     /// Export issues to Markdown
     Markdown {
@@ -42,6 +46,9 @@ pub enum ExportCommands {
         /// Export all matching issues
         #[arg(long)]
         all: bool,
+        /// Export from the local mirror instead of the API (see: linear mirror)
+        #[arg(long)]
+        local: bool,
     },
 }
 
@@ -53,25 +60,87 @@ pub async fn handle(cmd: ExportCommands, _output: &OutputOptions) -> Result<()>
             include_completed,
             limit,
             all,
-        } => export_csv(team, file, include_completed, limit, all).await,
+            local,
+        } => export_csv(team, file, include_completed, limit, all, local).await,
         ExportCommands::Markdown {
             team,
             file,
             limit,
             all,
-        } => export_markdown(team, file, limit, all).await,
+            local,
+        } => export_markdown(team, file, limit, all, local).await,
     }
 }
 
+/// Mirrored issues matching the export's team and completed filters
+fn local_issues(
+    team: Option<&str>,
+    include_completed: bool,
+    limit: Option<usize>,
+    all: bool,
+) -> Result<Vec<Value>> {
+    let limit = if all {
+        usize::MAX
+    } else {
+        limit.unwrap_or(250)
+    };
+    Ok(Mirror::load_synced()?
+        .issues(false)
+        .into_iter()
+        .filter(|issue| {
+            team.is_none_or(|t| {
+                issue["team"]["key"]
+                    .as_str()
+                    .is_some_and(|k| k.eq_ignore_ascii_case(t))
+            })
+        })
+        .filter(|issue| include_completed || issue["state"]["type"] != "completed")
+        .take(limit)
+        .collect())
+}
+
+fn write_csv_row(writer: &mut Writer<Box<dyn Write>>, issue: &Value) -> Result<()> {
+    let labels: Vec<&str> = issue["labels"]["nodes"]
+        .as_array()
+        .map(|a| a.iter().filter_map(|l| l["name"].as_str()).collect())
+        .unwrap_or_default();
+
+    writer.write_record([
+        issue["identifier"].as_str().unwrap_or(""),
+        issue["title"].as_str().unwrap_or(""),
+        issue["state"]["name"].as_str().unwrap_or(""),
+        &issue["priority"].as_i64().unwrap_or(0).to_string(),
+        &issue["estimate"].as_f64().unwrap_or(0.0).to_string(),
+        issue["dueDate"].as_str().unwrap_or(""),
+        issue["assignee"]["name"].as_str().unwrap_or(""),
+        issue["team"]["key"].as_str().unwrap_or(""),
+        issue["project"]["name"].as_str().unwrap_or(""),
+        issue["cycle"]["name"].as_str().unwrap_or(""),
+        &labels.join("; "),
+        &issue["createdAt"]
+            .as_str()
+            .unwrap_or("")
+            .chars()
+            .take(10)
+            .collect::<String>(),
+        &issue["updatedAt"]
+            .as_str()
+            .unwrap_or("")
+            .chars()
+            .take(10)
+            .collect::<String>(),
+    ])?;
+    Ok(())
+}
+
 async fn export_csv(
     team: Option<String>,
     file: Option<String>,
     include_completed: bool,
     limit: Option<usize>,
     all: bool,
+    local: bool,
 ) -> Result<()> {
-    let client = LinearClient::new()?;
-
     let query = r#"
         query($filter: IssueFilter, $first: Int, $after: String, $last: Int, $before: String) {
             issues(first: $first, after: $after, last: $last, before: $before, filter: $filter) {
@@ -153,57 +222,38 @@ async fn export_csv(
         "Updated",
     ])?;
 
-    // Stream pages and write rows as they arrive
-    let wtr_clone = Rc::clone(&wtr);
-    let total = stream_nodes(
-        &client,
-        query,
-        vars,
-        &["data", "issues", "nodes"],
-        &["data", "issues", "pageInfo"],
-        &pagination,
-        250,
-        |batch| {
-            let wtr = Rc::clone(&wtr_clone);
-            async move {
-                let mut writer = wtr.borrow_mut();
-                for issue in &batch {
-                    let labels: Vec<&str> = issue["labels"]["nodes"]
-                        .as_array()
-                        .map(|a| a.iter().filter_map(|l| l["name"].as_str()).collect())
-                        .unwrap_or_default();
-
-                    writer.write_record([
-                        issue["identifier"].as_str().unwrap_or(""),
-                        issue["title"].as_str().unwrap_or(""),
-                        issue["state"]["name"].as_str().unwrap_or(""),
-                        &issue["priority"].as_i64().unwrap_or(0).to_string(),
-                        &issue["estimate"].as_f64().unwrap_or(0.0).to_string(),
-                        issue["dueDate"].as_str().unwrap_or(""),
-                        issue["assignee"]["name"].as_str().unwrap_or(""),
-                        issue["team"]["key"].as_str().unwrap_or(""),
-                        issue["project"]["name"].as_str().unwrap_or(""),
-                        issue["cycle"]["name"].as_str().unwrap_or(""),
-                        &labels.join("; "),
-                        &issue["createdAt"]
-                            .as_str()
-                            .unwrap_or("")
-                            .chars()
-                            .take(10)
-                            .collect::<String>(),
-                        &issue["updatedAt"]
-                            .as_str()
-                            .unwrap_or("")
-                            .chars()
-                            .take(10)
-                            .collect::<String>(),
-                    ])?;
+    let total = if local {
+        let issues = local_issues(team.as_deref(), include_completed, limit, all)?;
+        let mut writer = wtr.borrow_mut();
+        for issue in &issues {
+            write_csv_row(&mut writer, issue)?;
+        }
+        issues.len()
+    } else {
+        // Stream pages and write rows as they arrive
+        let client = LinearClient::new()?;
+        let wtr_clone = Rc::clone(&wtr);
+        stream_nodes(
+            &client,
+            query,
+            vars,
+            &["data", "issues", "nodes"],
+            &["data", "issues", "pageInfo"],
+            &pagination,
+            250,
+            |batch| {
+                let wtr = Rc::clone(&wtr_clone);
+                async move {
+                    let mut writer = wtr.borrow_mut();
+                    for issue in &batch {
+                        write_csv_row(&mut writer, issue)?;
+                    }
+                    Ok(())
                 }
-                Ok(())
-            }
-        },
-    )
-    .await?;
+            },
+        )
+        .await?
+    };
 
     wtr.borrow_mut().flush()?;
 
@@ -219,9 +269,8 @@ async fn export_markdown(
     file: Option<String>,
     limit: Option<usize>,
     all: bool,
+    local: bool,
 ) -> Result<()> {
-    let client = LinearClient::new()?;
-
     let query = r#"
         query($filter: IssueFilter, $first: Int, $after: String, $last: Int, $before: String) {
             issues(first: $first, after: $after, last: $last, before: $before, filter: $filter) {
@@ -263,16 +312,21 @@ async fn export_markdown(
         pagination.limit = Some(limit.unwrap_or(250));
     }
 
-    let issues = paginate_nodes(
-        &client,
-        query,
-        vars,
-        &["data", "issues", "nodes"],
-        &["data", "issues", "pageInfo"],
-        &pagination,
-        250,
-    )
-    .await?;
+    let issues = if local {
+        local_issues(team.as_deref(), false, limit, all)?
+    } else {
+        let client = LinearClient::new()?;
+        paginate_nodes(
+            &client,
+            query,
+            vars,
+            &["data", "issues", "nodes"],
+            &["data", "issues", "pageInfo"],
+            &pagination,
+            250,
+        )
+        .await?
+    };
 
     let mut output: Box<dyn Write> = if let Some(ref path) = file {
         Box::new(std::fs::File::create(path)?)
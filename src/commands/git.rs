@@ -8,7 +8,10 @@ use std::process::Command;
 use crate::api::LinearClient;
 use crate::display_options;
 use crate::text::truncate;
-use crate::vcs::{generate_branch_name, run_git_command};
+use crate::vcs::{
+    branch_template, generate_branch_name, issue_branch_name, render_branch_template,
+    run_git_command,
+};
 
 /// Version control system type
 #[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
@@ -269,13 +272,8 @@ async fn checkout_issue(issue_id: &str, custom_branch: Option<String>, vcs: Vcs)
     let (identifier, title, linear_branch, url) = get_issue_info(issue_id).await?;
     let title_width = display_options().max_width(50);
 
-    let branch_name = custom_branch
-        .or(if linear_branch.is_empty() {
-            None
-        } else {
-            Some(linear_branch)
-        })
-        .unwrap_or_else(|| generate_branch_name(&identifier, &title));
+    let branch_name =
+        custom_branch.unwrap_or_else(|| issue_branch_name(&identifier, &title, &linear_branch));
 
     println!(
         "{} {} {}",
@@ -343,6 +341,12 @@ async fn show_branch(issue_id: &str, vcs: Vcs) -> Result<()> {
 
     let generated = generate_branch_name(&identifier, &title);
     println!("Generated:     {}", generated.yellow());
+    if let Some(template) = branch_template() {
+        println!(
+            "Template:      {}",
+            render_branch_template(&template, &identifier, &title).yellow()
+        );
+    }
     println!("Issue URL:     {}", url.blue());
 
     match vcs {
@@ -375,13 +379,8 @@ async fn create_branch(issue_id: &str, custom_branch: Option<String>, vcs: Vcs)
     let (identifier, title, linear_branch, url) = get_issue_info(issue_id).await?;
     let title_width = display_options().max_width(50);
 
-    let branch_name = custom_branch
-        .or(if linear_branch.is_empty() {
-            None
-        } else {
-            Some(linear_branch)
-        })
-        .unwrap_or_else(|| generate_branch_name(&identifier, &title));
+    let branch_name =
+        custom_branch.unwrap_or_else(|| issue_branch_name(&identifier, &title, &linear_branch));
 
     println!(
         "{} {} {}",
@@ -598,6 +597,18 @@ async fn create_pr(issue_id: &str, base: &str, draft: bool, web: bool) -> Result
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_render_branch_template() {
+        assert_eq!(
+            render_branch_template("{team}/{number}-{title}", "ENG-42", "Fix the login bug!"),
+            "eng/42-fix-the-login-bug"
+        );
+        assert_eq!(
+            render_branch_template("feature/{identifier}", "ENG-42", "Anything"),
+            "feature/eng-42"
+        );
+    }
+
     #[test]
     fn test_generate_branch_name_simple() {
         assert_eq!(
@@ -0,0 +1,159 @@
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::io::{self, Read};
+
+use crate::api::LinearClient;
+use crate::error::CliError;
+use crate::output::{print_json, OutputOptions};
+use crate::pagination::paginate_nodes;
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Send an arbitrary GraphQL document and print the result.
+///
+/// Without `--paginate` the `data` object is printed. With it, the named
+/// connection is followed through `pageInfo` and its nodes are printed as one
+/// array (all pages unless `--limit` is set); the query must declare `$first`
+/// and `$after` and pass them to that connection.
+pub async fn run(
+    query: &str,
+    vars_file: Option<&str>,
+    vars: &[String],
+    paginate: Option<&str>,
+    output: &OutputOptions,
+) -> Result<()> {
+    let query = read_source(query)?;
+    if query.trim().is_empty() {
+        return Err(CliError::new(1, "GraphQL query is empty").into());
+    }
+
+    let mut variables = match vars_file {
+        Some(source) => parse_vars_object(&read_source(source)?)?,
+        None => Map::new(),
+    };
+    for var in vars {
+        let (key, value) = parse_var(var)?;
+        variables.insert(key, value);
+    }
+
+    let client = LinearClient::new()?;
+
+    let Some(path) = paginate else {
+        let variables = (!variables.is_empty()).then_some(Value::Object(variables));
+        let result = client.query(&query, variables).await?;
+        return print_json(&result["data"], output);
+    };
+
+    let connection = connection_path(path)?;
+    let mut nodes_path: Vec<&str> = connection.iter().map(|s| s.as_str()).collect();
+    let mut page_info_path = nodes_path.clone();
+    nodes_path.push("nodes");
+    page_info_path.push("pageInfo");
+
+    let mut pagination = output.pagination.clone();
+    if pagination.limit.is_none() {
+        pagination.all = true;
+    }
+    let nodes = paginate_nodes(
+        &client,
+        &query,
+        variables,
+        &nodes_path,
+        &page_info_path,
+        &pagination,
+        DEFAULT_PAGE_SIZE,
+    )
+    .await?;
+
+    print_json(&Value::Array(nodes), output)
+}
+
+/// Read a literal argument, `@path` file reference, or `-` for stdin
+fn read_source(source: &str) -> Result<String> {
+    if source == "-" {
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .context("Failed to read stdin")?;
+        return Ok(buf);
+    }
+    if let Some(path) = source.strip_prefix('@') {
+        return std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path));
+    }
+    Ok(source.to_string())
+}
+
+fn parse_vars_object(raw: &str) -> Result<Map<String, Value>> {
+    match serde_json::from_str::<Value>(raw) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(CliError::new(1, "--vars must be a JSON object").into()),
+        Err(e) => Err(CliError::new(1, format!("Invalid --vars JSON: {}", e)).into()),
+    }
+}
+
+/// Parse `key=value`. Values that parse as JSON keep their type (numbers,
+/// booleans, null, arrays, objects); anything else is sent as a string.
+fn parse_var(raw: &str) -> Result<(String, Value)> {
+    let (key, value) = raw
+        .split_once('=')
+        .filter(|(k, _)| !k.trim().is_empty())
+        .ok_or_else(|| CliError::new(1, format!("Invalid --var '{}': expected key=value", raw)))?;
+    let value =
+        serde_json::from_str::<Value>(value).unwrap_or_else(|_| Value::String(value.to_string()));
+    Ok((key.trim().to_string(), value))
+}
+
+/// Turn `team.issues` (or `data.team.issues.nodes`) into the path of the
+/// connection object inside the response.
+fn connection_path(path: &str) -> Result<Vec<String>> {
+    let mut parts: Vec<String> = path
+        .split('.')
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    if parts.last().map(|p| p == "nodes").unwrap_or(false) {
+        parts.pop();
+    }
+    if parts.first().map(|p| p != "data").unwrap_or(false) {
+        parts.insert(0, "data".to_string());
+    }
+    if parts.len() < 2 {
+        return Err(CliError::new(1, format!("Invalid --paginate path '{}'", path)).into());
+    }
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_var_types() {
+        assert_eq!(parse_var("first=10").unwrap(), ("first".into(), json!(10)));
+        assert_eq!(parse_var("archived=true").unwrap().1, json!(true));
+        assert_eq!(parse_var("team=ENG").unwrap().1, json!("ENG"));
+        assert_eq!(parse_var("id=\"123\"").unwrap().1, json!("123"));
+        assert_eq!(parse_var("q=a=b").unwrap().1, json!("a=b"));
+        assert!(parse_var("novalue").is_err());
+        assert!(parse_var("=x").is_err());
+    }
+
+    #[test]
+    fn test_parse_vars_object_requires_object() {
+        assert_eq!(parse_vars_object(r#"{"a":1}"#).unwrap()["a"], json!(1));
+        assert!(parse_vars_object("[1]").is_err());
+        assert!(parse_vars_object("{").is_err());
+    }
+
+    #[test]
+    fn test_connection_path_normalization() {
+        assert_eq!(connection_path("issues").unwrap(), vec!["data", "issues"]);
+        assert_eq!(
+            connection_path("data.team.issues.nodes").unwrap(),
+            vec!["data", "team", "issues"]
+        );
+        assert!(connection_path("nodes").is_err());
+        assert!(connection_path("").is_err());
+    }
+}
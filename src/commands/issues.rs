@@ -8,15 +8,22 @@ use std::process::Command;
 use tabled::{Table, Tabled};
 
 use crate::api::{
-    resolve_issue_id, resolve_label_ids, resolve_project_id, resolve_state_id, resolve_team_id,
-    resolve_user_id, LinearClient,
+    resolve_cycle_id, resolve_issue_id, resolve_label_ids, resolve_project_id, resolve_state_id,
+    resolve_team_id, resolve_user_id, LinearClient,
 };
+use crate::cache::{self, CacheType};
+use crate::config;
 use crate::display_options;
+use crate::mirror::{IssueFilter, Mirror};
 use crate::output::{ensure_non_empty, filter_values, print_json, sort_values, OutputOptions};
-use crate::pagination::paginate_nodes;
+use crate::project_config;
+use crate::queries::{self, Fetched};
 use crate::text::truncate;
+use crate::types::Issue;
+use crate::vcs::issue_branch_name;
 use crate::AgentOptions;
 
+use super::outbox::{self, Operation};
 use super::{documents, templates};
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -41,6 +48,8 @@ pub enum IssueCommands {
     linear i list -t ENG -s "In Progress"      # Filter by team and status
     linear i list --assignee me                # Show my assigned issues
     linear i list --project "My Project"       # Filter by project name
+    linear i list --local -t ENG               # Query the local mirror
+    linear i list                              # Team/project from .linear.toml
     linear i list --output json                # Output as JSON"#)]
     List {
         /// Filter by team name or ID
@@ -70,6 +79,9 @@ pub enum IssueCommands {
         /// Include archived issues
         #[arg(long)]
         archived: bool,
+        /// Read from the local mirror instead of the API (see: linear mirror)
+        #[arg(long, conflicts_with = "initiative")]
+        local: bool,
     },
     /// Get issue details
     #[command(after_help = r#"EXAMPLES:
@@ -87,7 +99,8 @@ pub enum IssueCommands {
     linear issues create "Fix bug" -t ENG      # Create with title and team
     linear i create "Feature" -t ENG -p 2      # Create with high priority
     linear i create "Task" -t ENG -a me        # Assign to yourself
-    linear i create "Bug" -t ENG --dry-run     # Preview without creating"#)]
+    linear i create "Bug" -t ENG --dry-run     # Preview without creating
+    linear i create "Bug"                      # Team etc. from .linear.toml"#)]
     Create {
         /// Issue title
         title: String,
@@ -115,6 +128,9 @@ pub enum IssueCommands {
         /// Project name or ID
         #[arg(long)]
         project: Option<String>,
+        /// Cycle name, number or ID
+        #[arg(long)]
+        cycle: Option<String>,
         /// Estimate points
         #[arg(long)]
         estimate: Option<i32>,
@@ -375,12 +391,32 @@ pub async fn handle(
             initiative,
             due,
             archived,
+            local,
         } => {
-            list_issues(
-                team, state, assignee, project, label, cycle, initiative, due, archived, output,
-                agent_opts,
-            )
-            .await
+            let defaults = project_config::get();
+            let team = team.or_else(config::default_team);
+            let project = project.or_else(|| defaults.project.clone());
+            let issues = if local {
+                local_issues(
+                    IssueFilter {
+                        team,
+                        state,
+                        assignee,
+                        project,
+                        label,
+                        cycle,
+                        due: due.map(due_filter_to_comparator),
+                    },
+                    archived,
+                    output,
+                )?
+            } else {
+                fetch_issues(
+                    team, state, assignee, project, label, cycle, initiative, due, archived, output,
+                )
+                .await?
+            };
+            list_issues(issues, output, agent_opts)
         }
         IssueCommands::Get { ids } => {
             // Support reading from stdin if no IDs provided or if "-" is passed
@@ -414,12 +450,14 @@ pub async fn handle(
             assignee,
             labels,
             project,
+            cycle,
             estimate,
             due,
             parent,
             template,
             dry_run,
         } => {
+            let defaults = project_config::get();
             let dry_run = dry_run || output.dry_run || agent_opts.dry_run;
             // Load template if specified
             let tpl = if let Some(ref tpl_name) = template {
@@ -452,8 +490,12 @@ pub async fn handle(
                 .or(tpl.team.clone())
                 .or(data_team)
                 .or(data_team_id)
+                .or_else(config::default_team)
                 .ok_or_else(|| {
-                    anyhow::anyhow!("--team is required (or use a template with a default team)")
+                    anyhow::anyhow!(
+                        "--team is required (or use a template, or set a default team in {} or the profile)",
+                        project_config::FILE_NAME
+                    )
                 })?;
 
             // Build title with optional prefix from template
@@ -480,28 +522,53 @@ pub async fn handle(
             };
             let final_priority = priority.or(tpl.default_priority);
 
-            // Merge labels: template labels + CLI labels
+            // Merge labels: template labels + CLI labels (or the directory's defaults)
             let mut final_labels = tpl.default_labels.clone();
-            final_labels.extend(labels);
-
-            create_issue(
-                &final_title,
-                &final_team,
-                data_json,
-                final_description,
-                final_priority,
+            if labels.is_empty() {
+                final_labels.extend(defaults.labels.iter().cloned());
+            } else {
+                final_labels.extend(labels);
+            }
+            let project = project.or_else(|| defaults.project.clone());
+            let cycle = cycle.or_else(|| defaults.cycle.clone());
+
+            if dry_run {
+                return create_issue(
+                    &final_title,
+                    &final_team,
+                    data_json,
+                    final_description,
+                    final_priority,
+                    state,
+                    assignee,
+                    final_labels,
+                    project,
+                    cycle,
+                    estimate,
+                    due,
+                    parent,
+                    output,
+                    agent_opts,
+                    true,
+                )
+                .await;
+            }
+            let operation = Operation::IssueCreate(outbox::IssueCreate {
+                title: final_title,
+                team: final_team,
+                data: data_json,
+                description: final_description,
+                priority: final_priority,
                 state,
                 assignee,
-                final_labels,
+                labels: final_labels,
                 project,
+                cycle,
                 estimate,
                 due,
                 parent,
-                output,
-                agent_opts,
-                dry_run,
-            )
-            .await
+            });
+            outbox::submit(operation, output, agent_opts).await
         }
         IssueCommands::Update {
             id,
@@ -534,11 +601,31 @@ pub async fn handle(
                 Some(d) => Some(d.to_string()),
                 None => None,
             };
-            update_issue(
-                &id,
+            if dry_run {
+                return update_issue(
+                    &id,
+                    title,
+                    final_description,
+                    data_json,
+                    priority,
+                    state,
+                    assignee,
+                    labels,
+                    project,
+                    estimate,
+                    due,
+                    parent,
+                    true,
+                    output,
+                    agent_opts,
+                )
+                .await;
+            }
+            let operation = Operation::IssueUpdate(outbox::IssueUpdate {
+                id,
                 title,
-                final_description,
-                data_json,
+                description: final_description,
+                data: data_json,
                 priority,
                 state,
                 assignee,
@@ -547,11 +634,8 @@ pub async fn handle(
                 estimate,
                 due,
                 parent,
-                dry_run,
-                output,
-                agent_opts,
-            )
-            .await
+            });
+            outbox::submit(operation, output, agent_opts).await
         }
         IssueCommands::Delete { id, force } => delete_issue(&id, force, agent_opts).await,
         IssueCommands::Start {
@@ -688,7 +772,7 @@ fn parse_reminder_delay(input: &str) -> Result<Duration> {
 }
 
 #[allow(clippy::too_many_arguments)]
-async fn list_issues(
+async fn fetch_issues(
     team: Option<String>,
     state: Option<String>,
     assignee: Option<String>,
@@ -699,39 +783,9 @@ async fn list_issues(
     due: Option<DueFilter>,
     include_archived: bool,
     output: &OutputOptions,
-    _agent_opts: AgentOptions,
-) -> Result<()> {
+) -> Result<Vec<Fetched<Issue>>> {
     let client = LinearClient::new()?;
 
-    let query = r#"
-        query($filter: IssueFilter, $includeArchived: Boolean, $first: Int, $after: String, $last: Int, $before: String) {
-            issues(
-                first: $first,
-                after: $after,
-                last: $last,
-                before: $before,
-                includeArchived: $includeArchived,
-                filter: $filter
-            ) {
-                nodes {
-                    id
-                    identifier
-                    title
-                    priority
-                    dueDate
-                    state { name }
-                    assignee { name }
-                }
-                pageInfo {
-                    hasNextPage
-                    endCursor
-                    hasPreviousPage
-                    startCursor
-                }
-            }
-        }
-    "#;
-
     let mut variables = Map::new();
     variables.insert("includeArchived".to_string(), json!(include_archived));
 
@@ -798,19 +852,32 @@ async fn list_issues(
     }
 
     let pagination = output.pagination.with_default_limit(50);
-    let issues = paginate_nodes(
-        &client,
-        query,
-        variables,
-        &["data", "issues", "nodes"],
-        &["data", "issues", "pageInfo"],
-        &pagination,
-        50,
-    )
-    .await?;
+    queries::list_issues(&client, variables, &pagination).await
+}
 
+/// Mirrored issues matching `filter`, newest first, limited like the API list
+fn local_issues(
+    filter: IssueFilter,
+    include_archived: bool,
+    output: &OutputOptions,
+) -> Result<Vec<Fetched<Issue>>> {
+    let pagination = output.pagination.with_default_limit(50);
+    Mirror::load_synced()?
+        .issues(include_archived)
+        .into_iter()
+        .filter(|issue| filter.matches(issue))
+        .take(pagination.limit.unwrap_or(usize::MAX))
+        .map(|issue| Fetched::decode(issue, "issue"))
+        .collect()
+}
+
+fn list_issues(
+    issues: Vec<Fetched<Issue>>,
+    output: &OutputOptions,
+    _agent_opts: AgentOptions,
+) -> Result<()> {
     if output.is_json() || output.has_template() {
-        print_json(&serde_json::json!(issues), output)?;
+        print_json(&queries::raw_values(&issues), output)?;
         return Ok(());
     }
 
@@ -830,15 +897,20 @@ async fn list_issues(
     let width = display_options().max_width(50);
     let rows: Vec<IssueRow> = issues
         .iter()
-        .map(|issue| IssueRow {
-            identifier: issue["identifier"].as_str().unwrap_or("").to_string(),
-            title: truncate(issue["title"].as_str().unwrap_or(""), width),
-            state: issue["state"]["name"].as_str().unwrap_or("-").to_string(),
-            priority: priority_to_string(issue["priority"].as_i64()),
-            assignee: issue["assignee"]["name"]
-                .as_str()
-                .unwrap_or("-")
-                .to_string(),
+        .map(|Fetched { data: issue, .. }| IssueRow {
+            identifier: issue.identifier.clone(),
+            title: truncate(&issue.title, width),
+            state: issue
+                .state
+                .as_ref()
+                .map(|s| s.name.clone())
+                .unwrap_or_else(|| "-".to_string()),
+            priority: priority_to_string(Some(issue.priority as i64)),
+            assignee: issue
+                .assignee
+                .as_ref()
+                .map(|a| a.name.clone())
+                .unwrap_or_else(|| "-".to_string()),
         })
         .collect();
 
@@ -858,53 +930,14 @@ async fn get_issues(ids: &[String], output: &OutputOptions) -> Result<()> {
 
     let client = LinearClient::new()?;
 
-    // For multiple IDs, fetch them in parallel
-    let futures: Vec<_> = ids
-        .iter()
-        .map(|id| {
-            let client = client.clone();
-            let id = id.clone();
-            async move {
-                let query = r#"
-                    query($id: String!) {
-                        issue(id: $id) {
-                            id
-                            identifier
-                            title
-                            description
-                            priority
-                            dueDate
-                            url
-                            attachments { nodes { id title url createdAt } }
-                            documents { nodes { id title url updatedAt } }
-                            state { name }
-                            team { name }
-                            assignee { name }
-                        }
-                    }
-                "#;
-                let result = client.query(query, Some(json!({ "id": id }))).await;
-                (id, result)
-            }
-        })
-        .collect();
-
-    let results = futures::future::join_all(futures).await;
+    // For multiple IDs, fetch them in aliased batches
+    let results = queries::get_issues(&client, ids).await?;
 
     // JSON output: array of issues
     if output.is_json() || output.has_template() {
         let issues: Vec<_> = results
             .iter()
-            .filter_map(|(_, r)| {
-                r.as_ref().ok().and_then(|data| {
-                    let issue = &data["data"]["issue"];
-                    if !issue.is_null() {
-                        Some(issue.clone())
-                    } else {
-                        None
-                    }
-                })
-            })
+            .filter_map(|(_, r)| r.as_ref().ok().map(|issue| issue.raw.clone()))
             .collect();
         print_json(&serde_json::json!(issues), output)?;
         return Ok(());
@@ -913,17 +946,19 @@ async fn get_issues(ids: &[String], output: &OutputOptions) -> Result<()> {
     // Table output
     for (id, result) in results {
         match result {
-            Ok(data) => {
-                let issue = &data["data"]["issue"];
-                if issue.is_null() {
-                    eprintln!("{} Issue not found: {}", "!".yellow(), id);
-                } else {
-                    let identifier = issue["identifier"].as_str().unwrap_or("");
-                    let title = issue["title"].as_str().unwrap_or("");
-                    let state = issue["state"]["name"].as_str().unwrap_or("-");
-                    let priority = priority_to_string(issue["priority"].as_i64());
-                    println!("{} {} [{}] {}", identifier.cyan(), title, state, priority);
-                }
+            Ok(Fetched { data: issue, .. }) => {
+                let state = issue.state.as_ref().map(|s| s.name.as_str()).unwrap_or("-");
+                let priority = priority_to_string(Some(issue.priority as i64));
+                println!(
+                    "{} {} [{}] {}",
+                    issue.identifier.cyan(),
+                    issue.title,
+                    state,
+                    priority
+                );
+            }
+            Err(e) if queries::is_not_found(&e) => {
+                eprintln!("{} Issue not found: {}", "!".yellow(), id);
             }
             Err(e) => {
                 eprintln!("{} Error fetching {}: {}", "!".red(), id, e);
@@ -936,50 +971,18 @@ async fn get_issues(ids: &[String], output: &OutputOptions) -> Result<()> {
 
 async fn get_issue(id: &str, output: &OutputOptions) -> Result<()> {
     let client = LinearClient::new()?;
-
-    let query = r#"
-        query($id: String!) {
-            issue(id: $id) {
-                id
-                identifier
-                title
-                description
-                priority
-                dueDate
-                url
-                createdAt
-                updatedAt
-                attachments { nodes { id title url createdAt } }
-                documents { nodes { id title url updatedAt } }
-                state { name }
-                team { name }
-                assignee { name email }
-                labels { nodes { name color } }
-                project { name }
-                parent { identifier title }
-            }
-        }
-    "#;
-
-    let result = client.query(query, Some(json!({ "id": id }))).await?;
-    let issue = &result["data"]["issue"];
-
-    if issue.is_null() {
-        anyhow::bail!("Issue not found: {}", id);
-    }
+    let Fetched { raw, data: issue } = queries::get_issue(&client, id).await?;
 
     // Handle JSON output
     if output.is_json() || output.has_template() {
-        print_json(issue, output)?;
+        print_json(&raw, output)?;
         return Ok(());
     }
 
-    let identifier = issue["identifier"].as_str().unwrap_or("");
-    let title = issue["title"].as_str().unwrap_or("");
-    println!("{} {}", identifier.cyan().bold(), title.bold());
+    println!("{} {}", issue.identifier.cyan().bold(), issue.title.bold());
     println!("{}", "-".repeat(60));
 
-    if let Some(desc) = issue["description"].as_str() {
+    if let Some(desc) = issue.description.as_deref() {
         if !desc.is_empty() {
             println!("\n{}", desc);
             println!();
@@ -988,60 +991,56 @@ async fn get_issue(id: &str, output: &OutputOptions) -> Result<()> {
 
     println!(
         "State:    {}",
-        issue["state"]["name"].as_str().unwrap_or("-")
+        issue.state.as_ref().map(|s| s.name.as_str()).unwrap_or("-")
     );
     println!(
         "Priority: {}",
-        priority_to_string(issue["priority"].as_i64())
+        priority_to_string(Some(issue.priority as i64))
     );
     println!(
         "Team:     {}",
-        issue["team"]["name"].as_str().unwrap_or("-")
+        issue.team.as_ref().map(|t| t.name.as_str()).unwrap_or("-")
     );
 
-    if let Some(assignee) = issue["assignee"]["name"].as_str() {
-        let email = issue["assignee"]["email"].as_str().unwrap_or("");
-        if !email.is_empty() {
-            println!("Assignee: {} ({})", assignee, email.dimmed());
-        } else {
-            println!("Assignee: {}", assignee);
-        }
-    } else {
-        println!("Assignee: -");
+    match &issue.assignee {
+        Some(assignee) => match assignee.email.as_deref().filter(|e| !e.is_empty()) {
+            Some(email) => println!("Assignee: {} ({})", assignee.name, email.dimmed()),
+            None => println!("Assignee: {}", assignee.name),
+        },
+        None => println!("Assignee: -"),
     }
 
-    if let Some(project) = issue["project"]["name"].as_str() {
-        println!("Project:  {}", project);
+    if let Some(project) = &issue.project {
+        println!("Project:  {}", project.name);
     }
 
-    if let Some(parent) = issue["parent"]["identifier"].as_str() {
-        let parent_title = issue["parent"]["title"].as_str().unwrap_or("");
-        println!("Parent:   {} {}", parent, parent_title.dimmed());
+    if let Some(parent) = &issue.parent {
+        let parent_title = parent.title.as_deref().unwrap_or("");
+        println!("Parent:   {} {}", parent.identifier, parent_title.dimmed());
     }
 
-    let labels = issue["labels"]["nodes"].as_array();
-    if let Some(labels) = labels {
-        if !labels.is_empty() {
-            let label_names: Vec<&str> = labels.iter().filter_map(|l| l["name"].as_str()).collect();
+    if let Some(labels) = &issue.labels {
+        if !labels.nodes.is_empty() {
+            let label_names: Vec<&str> = labels.nodes.iter().map(|l| l.name.as_str()).collect();
             println!("Labels:   {}", label_names.join(", "));
         }
     }
 
-    if let Some(docs) = issue["documents"]["nodes"].as_array() {
-        if !docs.is_empty() {
-            let doc_refs: Vec<&str> = docs.iter().filter_map(|d| d["title"].as_str()).collect();
+    if let Some(docs) = &issue.documents {
+        if !docs.nodes.is_empty() {
+            let doc_refs: Vec<&str> = docs.nodes.iter().map(|d| d.title.as_str()).collect();
             println!("Docs:     {}", doc_refs.join(", "));
         }
     }
 
-    println!("\nURL: {}", issue["url"].as_str().unwrap_or("-"));
-    println!("ID:  {}", issue["id"].as_str().unwrap_or("-"));
+    println!("\nURL: {}", issue.url.as_deref().unwrap_or("-"));
+    println!("ID:  {}", issue.id);
 
     Ok(())
 }
 
 #[allow(clippy::too_many_arguments)]
-async fn create_issue(
+pub async fn create_issue(
     title: &str,
     team: &str,
     data_json: Option<Value>,
@@ -1051,6 +1050,7 @@ async fn create_issue(
     assignee: Option<String>,
     labels: Vec<String>,
     project: Option<String>,
+    cycle: Option<String>,
     estimate: Option<i32>,
     due: Option<String>,
     parent: Option<String>,
@@ -1112,6 +1112,10 @@ async fn create_issue(
         let project_id = resolve_project_id(&client, p, false).await?;
         input["projectId"] = json!(project_id);
     }
+    if let Some(ref c) = cycle {
+        let cycle_id = resolve_cycle_id(&client, &team_id, c).await?;
+        input["cycleId"] = json!(cycle_id);
+    }
     if let Some(points) = estimate {
         input["estimate"] = json!(points);
     }
@@ -1139,6 +1143,7 @@ async fn create_issue(
                         "assignee": assignee,
                         "labels": labels,
                         "project": project,
+                        "cycle": cycle,
                         "estimate": estimate,
                         "due": due,
                         "parent": parent
@@ -1173,6 +1178,9 @@ async fn create_issue(
             if let Some(ref p) = project {
                 println!("  Project:     {}", p);
             }
+            if let Some(ref c) = cycle {
+                println!("  Cycle:       {}", c);
+            }
             if let Some(points) = estimate {
                 println!("  Estimate:    {}", points);
             }
@@ -1199,14 +1207,28 @@ async fn create_issue(
             }
         }
     "#;
+    let lookup = r#"
+        query($id: String!) {
+            issue(id: $id) {
+                id
+                identifier
+                title
+                url
+            }
+        }
+    "#;
 
     let result = client
-        .mutate(mutation, Some(json!({ "input": input })))
+        .create_entity(mutation, input, "issueCreate", "issue", lookup)
         .await?;
 
     if result["data"]["issueCreate"]["success"].as_bool() == Some(true) {
         let issue = &result["data"]["issueCreate"]["issue"];
         let identifier = issue["identifier"].as_str().unwrap_or("");
+        if let Some(id) = issue["id"].as_str().filter(|_| !identifier.is_empty()) {
+            // Chained commands usually refer to the new issue next
+            cache::remember(CacheType::IssueIds, identifier, id);
+        }
 
         // --id-only: Just output the identifier for chaining
         if agent_opts.id_only {
@@ -1243,7 +1265,7 @@ async fn create_issue(
 }
 
 #[allow(clippy::too_many_arguments)]
-async fn update_issue(
+pub async fn update_issue(
     id: &str,
     title: Option<String>,
     description: Option<String>,
@@ -1353,6 +1375,14 @@ async fn update_issue(
         let issue = &result["data"]["issueUpdate"]["issue"];
         let identifier = issue["identifier"].as_str().unwrap_or("");
 
+        if input.get("teamId").is_some() {
+            // Moving teams gives the issue a new identifier
+            cache::forget(CacheType::IssueIds, |_, id| id == issue_id);
+            if !identifier.is_empty() {
+                cache::remember(CacheType::IssueIds, identifier, &issue_id);
+            }
+        }
+
         // --id-only: Just output the identifier
         if agent_opts.id_only {
             println!("{}", identifier);
@@ -1753,28 +1783,6 @@ fn branch_exists(branch: &str) -> bool {
     run_git_command(&["rev-parse", "--verify", branch]).is_ok()
 }
 
-fn generate_branch_name(identifier: &str, title: &str) -> String {
-    // Convert title to kebab-case for branch name
-    let slug: String = title
-        .to_lowercase()
-        .chars()
-        .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .collect::<String>()
-        .split('-')
-        .filter(|s| !s.is_empty())
-        .collect::<Vec<_>>()
-        .join("-");
-
-    // Truncate if too long
-    let slug = if slug.len() > 50 {
-        slug[..50].trim_end_matches('-').to_string()
-    } else {
-        slug
-    };
-
-    format!("{}/{}", identifier.to_lowercase(), slug)
-}
-
 async fn start_issue(
     id: &str,
     checkout: bool,
@@ -1895,13 +1903,8 @@ async fn start_issue(
 
     // Optionally checkout a git branch
     if checkout {
-        let branch_name = custom_branch
-            .or(if linear_branch.is_empty() {
-                None
-            } else {
-                Some(linear_branch)
-            })
-            .unwrap_or_else(|| generate_branch_name(identifier, title));
+        let branch_name =
+            custom_branch.unwrap_or_else(|| issue_branch_name(identifier, title, &linear_branch));
 
         if !agent_opts.quiet {
             println!();
@@ -0,0 +1,661 @@
+use anyhow::Result;
+use clap::Subcommand;
+use serde_json::{json, Map, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use super::{comments, issues, projects, search};
+use crate::error::CliError;
+use crate::output::{self, OutputOptions};
+use crate::{AgentOptions, OutputFormat};
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+#[derive(Subcommand)]
+pub enum McpCommands {
+    /// Serve MCP (Model Context Protocol) JSON-RPC over stdio
+    #[command(after_help = r#"EXAMPLES:
+    linear mcp serve                         # Start server on stdin/stdout
+    linear mcp serve --profile work          # Serve a specific profile
+
+Register with an MCP client, e.g.:
+    { "command": "linear-cli", "args": ["mcp", "serve"] }"#)]
+    Serve,
+    /// List the tools exposed by the MCP server
+    Tools,
+}
+
+/// A tool exposed over MCP
+struct Tool {
+    name: &'static str,
+    description: &'static str,
+    schema: fn() -> Value,
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        name: "issues_list",
+        description: "List issues, optionally filtered by team, state, assignee, project or label",
+        schema: issues_list_schema,
+    },
+    Tool {
+        name: "issues_get",
+        description: "Get one or more issues by identifier (e.g. ENG-123) or UUID",
+        schema: issues_get_schema,
+    },
+    Tool {
+        name: "issues_create",
+        description: "Create an issue",
+        schema: issues_create_schema,
+    },
+    Tool {
+        name: "issues_update",
+        description: "Update fields of an existing issue",
+        schema: issues_update_schema,
+    },
+    Tool {
+        name: "projects_list",
+        description: "List projects",
+        schema: projects_list_schema,
+    },
+    Tool {
+        name: "projects_get",
+        description: "Get one or more projects by name or UUID",
+        schema: projects_get_schema,
+    },
+    Tool {
+        name: "comments_list",
+        description: "List comments on an issue",
+        schema: comments_list_schema,
+    },
+    Tool {
+        name: "comments_create",
+        description: "Add a comment to an issue",
+        schema: comments_create_schema,
+    },
+    Tool {
+        name: "search_issues",
+        description: "Full-text search across issues",
+        schema: search_schema,
+    },
+    Tool {
+        name: "search_projects",
+        description: "Full-text search across projects",
+        schema: search_schema,
+    },
+];
+
+fn issues_list_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "team": { "type": "string", "description": "Team key or name" },
+            "state": { "type": "string", "description": "Workflow state name" },
+            "assignee": { "type": "string", "description": "Assignee name" },
+            "project": { "type": "string", "description": "Project name" },
+            "label": { "type": "string", "description": "Label name or ID" },
+            "archived": { "type": "boolean", "description": "Include archived issues" },
+            "limit": { "type": "integer", "minimum": 1, "description": "Maximum results (default 50)" }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn issues_get_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "ids": {
+                "type": "array",
+                "items": { "type": "string" },
+                "minItems": 1,
+                "description": "Issue identifiers or UUIDs"
+            }
+        },
+        "required": ["ids"],
+        "additionalProperties": false
+    })
+}
+
+fn issues_create_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "title": { "type": "string" },
+            "team": { "type": "string", "description": "Team key, name or UUID" },
+            "description": { "type": "string", "description": "Markdown description" },
+            "priority": { "type": "integer", "minimum": 0, "maximum": 4, "description": "0=none, 1=urgent, 2=high, 3=normal, 4=low" },
+            "state": { "type": "string", "description": "Workflow state name or UUID" },
+            "assignee": { "type": "string", "description": "User name, email, UUID or \"me\"" },
+            "labels": { "type": "array", "items": { "type": "string" } },
+            "project": { "type": "string", "description": "Project name or UUID" },
+            "cycle": { "type": "string", "description": "Cycle name, number or UUID" },
+            "due": { "type": "string", "description": "Due date (YYYY-MM-DD)" },
+            "parent": { "type": "string", "description": "Parent issue identifier or UUID" }
+        },
+        "required": ["title", "team"],
+        "additionalProperties": false
+    })
+}
+
+fn issues_update_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": { "type": "string", "description": "Issue identifier or UUID" },
+            "title": { "type": "string" },
+            "description": { "type": "string" },
+            "priority": { "type": "integer", "minimum": 0, "maximum": 4 },
+            "state": { "type": "string" },
+            "assignee": { "type": "string" },
+            "labels": { "type": "array", "items": { "type": "string" }, "description": "Replaces existing labels" },
+            "project": { "type": "string" },
+            "due": { "type": "string" },
+            "parent": { "type": "string" }
+        },
+        "required": ["id"],
+        "additionalProperties": false
+    })
+}
+
+fn projects_list_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "archived": { "type": "boolean", "description": "Include archived projects" }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn projects_get_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "ids": {
+                "type": "array",
+                "items": { "type": "string" },
+                "minItems": 1,
+                "description": "Project names or UUIDs"
+            }
+        },
+        "required": ["ids"],
+        "additionalProperties": false
+    })
+}
+
+fn comments_list_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "issue": { "type": "string", "description": "Issue identifier or UUID" }
+        },
+        "required": ["issue"],
+        "additionalProperties": false
+    })
+}
+
+fn comments_create_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "issue": { "type": "string", "description": "Issue identifier or UUID" },
+            "body": { "type": "string", "description": "Markdown comment body" },
+            "parent_id": { "type": "string", "description": "Comment ID to reply to" }
+        },
+        "required": ["issue", "body"],
+        "additionalProperties": false
+    })
+}
+
+fn search_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "query": { "type": "string" },
+            "archived": { "type": "boolean", "description": "Include archived results" },
+            "limit": { "type": "integer", "minimum": 1 }
+        },
+        "required": ["query"],
+        "additionalProperties": false
+    })
+}
+
+pub async fn handle(cmd: McpCommands, output: &OutputOptions) -> Result<()> {
+    match cmd {
+        McpCommands::Serve => serve(output).await,
+        McpCommands::Tools => {
+            output::print_json(&tool_list(), output)?;
+            Ok(())
+        }
+    }
+}
+
+fn tool_list() -> Value {
+    let tools: Vec<Value> = TOOLS
+        .iter()
+        .map(|tool| {
+            json!({
+                "name": tool.name,
+                "description": tool.description,
+                "inputSchema": (tool.schema)(),
+            })
+        })
+        .collect();
+    json!(tools)
+}
+
+/// Run the JSON-RPC loop until stdin closes. Messages are newline-delimited.
+async fn serve(output: &OutputOptions) -> Result<()> {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = protocol_stdout()?;
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(response) = handle_message(&line, output).await else {
+            continue;
+        };
+        let mut text = serde_json::to_string(&response)?;
+        text.push('\n');
+        stdout.write_all(text.as_bytes()).await?;
+        stdout.flush().await?;
+    }
+
+    Ok(())
+}
+
+/// Writer for JSON-RPC responses. On Unix the original stdout is duplicated
+/// for the protocol and fd 1 is pointed at stderr for the rest of the
+/// session, so text a command handler prints can't corrupt the stream.
+#[cfg(unix)]
+fn protocol_stdout() -> Result<tokio::fs::File> {
+    use std::io::Write;
+    use std::os::fd::AsFd;
+
+    let stdout = std::io::stdout();
+    stdout.lock().flush()?;
+    let protocol = stdout.as_fd().try_clone_to_owned()?;
+    // SAFETY: dup2 on the process's own standard descriptors
+    if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    Ok(tokio::fs::File::from_std(std::fs::File::from(protocol)))
+}
+
+#[cfg(not(unix))]
+fn protocol_stdout() -> Result<tokio::io::Stdout> {
+    Ok(tokio::io::stdout())
+}
+
+/// Handle one JSON-RPC message; notifications produce no response.
+async fn handle_message(line: &str, output: &OutputOptions) -> Option<Value> {
+    let message: Value = match serde_json::from_str(line) {
+        Ok(message) => message,
+        Err(e) => return Some(rpc_error(Value::Null, PARSE_ERROR, &e.to_string())),
+    };
+
+    let id = message.get("id").cloned();
+    let Some(method) = message["method"].as_str() else {
+        return Some(rpc_error(
+            id.unwrap_or(Value::Null),
+            INVALID_REQUEST,
+            "Missing method",
+        ));
+    };
+    // Notifications (no id) never get a response
+    let id = id?;
+
+    let params = message.get("params").cloned().unwrap_or(json!({}));
+    let result = match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": {
+                "name": "linear-cli",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_list() })),
+        "tools/call" => call_tool(&params, output).await,
+        _ => Err((METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+    };
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => rpc_error(id, code, &message),
+    })
+}
+
+fn rpc_error(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+/// Execute a tool call. Protocol-level problems become JSON-RPC errors;
+/// failures inside the tool are reported as an `isError` result.
+async fn call_tool(params: &Value, output: &OutputOptions) -> Result<Value, (i64, String)> {
+    let name = params["name"]
+        .as_str()
+        .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+    if !TOOLS.iter().any(|t| t.name == name) {
+        return Err((INVALID_PARAMS, format!("Unknown tool: {}", name)));
+    }
+    let empty = Map::new();
+    let args = params["arguments"].as_object().unwrap_or(&empty);
+
+    let mut tool_output = output.clone();
+    tool_output.format = OutputFormat::Json;
+    tool_output.format_template = None;
+    tool_output.filters = Vec::new();
+    tool_output.fail_on_empty = false;
+    tool_output.json.fields = None;
+    if let Some(limit) = args.get("limit").and_then(|v| v.as_u64()) {
+        tool_output.pagination.limit = Some(limit as usize);
+    }
+
+    output::begin_capture();
+    let result = run_tool(name, args, &tool_output).await;
+    let captured = output::end_capture();
+
+    Ok(match result {
+        Ok(()) => {
+            let value = match captured.len() {
+                0 => json!({ "success": true }),
+                1 => captured.into_iter().next().unwrap_or(Value::Null),
+                _ => json!(captured),
+            };
+            json!({
+                "content": [{ "type": "text", "text": value.to_string() }],
+                "isError": false,
+            })
+        }
+        Err(e) => {
+            let error = match e.downcast_ref::<CliError>() {
+                Some(cli) => json!({
+                    "message": e.to_string(),
+                    "code": cli.code,
+                    "details": cli.details,
+                }),
+                None => json!({ "message": e.to_string() }),
+            };
+            json!({
+                "content": [{ "type": "text", "text": error.to_string() }],
+                "isError": true,
+            })
+        }
+    })
+}
+
+async fn run_tool(name: &str, args: &Map<String, Value>, output: &OutputOptions) -> Result<()> {
+    reject_stdin_args(args)?;
+    let agent_opts = AgentOptions::default();
+    match name {
+        "issues_list" => {
+            let cmd = issues::IssueCommands::List {
+                team: opt_str(args, "team"),
+                state: opt_str(args, "state"),
+                assignee: opt_str(args, "assignee"),
+                project: opt_str(args, "project"),
+                label: opt_str(args, "label"),
+                cycle: None,
+                initiative: None,
+                due: None,
+                archived: opt_bool(args, "archived"),
+                local: false,
+            };
+            issues::handle(cmd, output, agent_opts).await
+        }
+        "issues_get" => {
+            let cmd = issues::IssueCommands::Get {
+                ids: req_str_list(args, "ids")?,
+            };
+            issues::handle(cmd, output, agent_opts).await
+        }
+        "issues_create" => {
+            let cmd = issues::IssueCommands::Create {
+                title: req_str(args, "title")?,
+                team: Some(req_str(args, "team")?),
+                description: opt_str(args, "description"),
+                data: None,
+                priority: opt_i32(args, "priority"),
+                state: opt_str(args, "state"),
+                assignee: opt_str(args, "assignee"),
+                labels: opt_str_list(args, "labels"),
+                project: opt_str(args, "project"),
+                cycle: opt_str(args, "cycle"),
+                estimate: None,
+                due: opt_str(args, "due"),
+                parent: opt_str(args, "parent"),
+                template: None,
+                dry_run: false,
+            };
+            issues::handle(cmd, output, agent_opts).await
+        }
+        "issues_update" => {
+            let cmd = issues::IssueCommands::Update {
+                id: req_str(args, "id")?,
+                title: opt_str(args, "title"),
+                description: opt_str(args, "description"),
+                data: None,
+                priority: opt_i32(args, "priority"),
+                state: opt_str(args, "state"),
+                assignee: opt_str(args, "assignee"),
+                labels: opt_str_list(args, "labels"),
+                project: opt_str(args, "project"),
+                estimate: None,
+                due: opt_str(args, "due"),
+                parent: opt_str(args, "parent"),
+                dry_run: false,
+            };
+            if !has_updates(&cmd) {
+                return Err(CliError::new(5, "No updates specified").into());
+            }
+            issues::handle(cmd, output, agent_opts).await
+        }
+        "projects_list" => {
+            let cmd = projects::ProjectCommands::List {
+                archived: opt_bool(args, "archived"),
+                project_statuses: false,
+            };
+            projects::handle(cmd, output).await
+        }
+        "projects_get" => {
+            let cmd = projects::ProjectCommands::Get {
+                ids: req_str_list(args, "ids")?,
+            };
+            projects::handle(cmd, output).await
+        }
+        "comments_list" => {
+            let cmd = comments::CommentCommands::List {
+                issue_ids: vec![req_str(args, "issue")?],
+            };
+            comments::handle(cmd, output, agent_opts).await
+        }
+        "comments_create" => {
+            let cmd = comments::CommentCommands::Create {
+                issue_id: req_str(args, "issue")?,
+                body: req_str(args, "body")?,
+                parent_id: opt_str(args, "parent_id"),
+            };
+            comments::handle(cmd, output, agent_opts).await
+        }
+        "search_issues" => {
+            let cmd = search::SearchCommands::Issues {
+                query: req_str(args, "query")?,
+                archived: opt_bool(args, "archived"),
+                local: false,
+            };
+            search::handle(cmd, output).await
+        }
+        "search_projects" => {
+            let cmd = search::SearchCommands::Projects {
+                query: req_str(args, "query")?,
+                archived: opt_bool(args, "archived"),
+            };
+            search::handle(cmd, output).await
+        }
+        _ => anyhow::bail!("Unknown tool: {}", name),
+    }
+}
+
+/// Stdin is the protocol stream, so nothing may ask a command to read it:
+/// no `"-"` values and no empty lists (an empty ID list means "from stdin")
+fn reject_stdin_args(args: &Map<String, Value>) -> Result<()> {
+    for (key, value) in args {
+        let reads_stdin = match value {
+            Value::String(s) => s.trim() == "-",
+            Value::Array(items) => {
+                items.is_empty()
+                    || items
+                        .iter()
+                        .any(|v| v.as_str().is_some_and(|s| s.trim() == "-"))
+            }
+            _ => false,
+        };
+        if reads_stdin {
+            return Err(CliError::new(
+                5,
+                format!("Argument '{}' can't be \"-\" or empty under mcp serve", key),
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Whether an `issues_update` call changes anything; null, unknown and
+/// mistyped arguments don't count
+fn has_updates(cmd: &issues::IssueCommands) -> bool {
+    match cmd {
+        issues::IssueCommands::Update {
+            title,
+            description,
+            priority,
+            state,
+            assignee,
+            labels,
+            project,
+            due,
+            parent,
+            ..
+        } => {
+            title.is_some()
+                || description.is_some()
+                || priority.is_some()
+                || state.is_some()
+                || assignee.is_some()
+                || !labels.is_empty()
+                || project.is_some()
+                || due.is_some()
+                || parent.is_some()
+        }
+        _ => true,
+    }
+}
+
+fn opt_str(args: &Map<String, Value>, key: &str) -> Option<String> {
+    args.get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
+fn req_str(args: &Map<String, Value>, key: &str) -> Result<String> {
+    opt_str(args, key)
+        .ok_or_else(|| CliError::new(1, format!("Missing required argument: {}", key)).into())
+}
+
+fn opt_bool(args: &Map<String, Value>, key: &str) -> bool {
+    args.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+fn opt_i32(args: &Map<String, Value>, key: &str) -> Option<i32> {
+    args.get(key)
+        .and_then(|v| v.as_i64())
+        .and_then(|v| i32::try_from(v).ok())
+}
+
+fn opt_str_list(args: &Map<String, Value>, key: &str) -> Vec<String> {
+    match args.get(key) {
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        Some(Value::String(s)) => vec![s.clone()],
+        _ => Vec::new(),
+    }
+}
+
+fn req_str_list(args: &Map<String, Value>, key: &str) -> Result<Vec<String>> {
+    let values = opt_str_list(args, key);
+    if values.is_empty() {
+        return Err(CliError::new(1, format!("Missing required argument: {}", key)).into());
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_names_are_unique() {
+        let mut names: Vec<&str> = TOOLS.iter().map(|t| t.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), TOOLS.len());
+    }
+
+    #[test]
+    fn test_tool_schemas_are_objects() {
+        for tool in TOOLS {
+            let schema = (tool.schema)();
+            assert_eq!(schema["type"], "object", "{}", tool.name);
+            if let Some(required) = schema["required"].as_array() {
+                for field in required {
+                    let field = field.as_str().unwrap();
+                    assert!(
+                        schema["properties"].get(field).is_some(),
+                        "{} requires undeclared {}",
+                        tool.name,
+                        field
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_stdin_arguments_are_rejected() {
+        let ok = json!({ "ids": ["ENG-1"], "description": "a - b", "priority": 1 });
+        assert!(reject_stdin_args(ok.as_object().unwrap()).is_ok());
+        for bad in [
+            json!({ "ids": ["-"] }),
+            json!({ "ids": [] }),
+            json!({ "description": "-" }),
+            json!({ "body": " - " }),
+        ] {
+            let err = reject_stdin_args(bad.as_object().unwrap()).unwrap_err();
+            assert_eq!(err.downcast_ref::<CliError>().unwrap().code, 5);
+        }
+    }
+
+    #[test]
+    fn test_opt_str_list_accepts_string_or_array() {
+        let args = json!({ "a": ["x", "y"], "b": "z" });
+        let args = args.as_object().unwrap();
+        assert_eq!(opt_str_list(args, "a"), vec!["x", "y"]);
+        assert_eq!(opt_str_list(args, "b"), vec!["z"]);
+        assert!(opt_str_list(args, "c").is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::Result;
+use clap::Subcommand;
+use colored::Colorize;
+use serde_json::{json, Value};
+use tabled::{Table, Tabled};
+
+use crate::api::{resolve_team_id, LinearClient};
+use crate::error::CliError;
+use crate::mirror::{self, Mirror};
+use crate::output::{print_json, OutputOptions};
+
+#[derive(Subcommand)]
+pub enum MirrorCommands {
+    /// Pull issues into the local mirror, fetching only what changed since the last sync
+    Sync {
+        /// Team key, name or ID to mirror (repeatable; default: teams already mirrored)
+        #[arg(short, long)]
+        team: Vec<String>,
+        /// Also mirror the latest comments of each issue
+        #[arg(long)]
+        comments: bool,
+        /// Re-fetch everything instead of only issues updated since the last sync
+        #[arg(long)]
+        full: bool,
+    },
+    /// Show mirrored teams, issue counts and last sync times
+    Status,
+    /// Delete the local mirror
+    Clear,
+}
+
+#[derive(Tabled)]
+struct MirrorRow {
+    #[tabled(rename = "Team")]
+    team: String,
+    #[tabled(rename = "Issues")]
+    issues: usize,
+    #[tabled(rename = "Comments")]
+    comments: String,
+    #[tabled(rename = "Last Sync")]
+    synced_at: String,
+}
+
+pub async fn handle(cmd: MirrorCommands, output: &OutputOptions) -> Result<()> {
+    match cmd {
+        MirrorCommands::Sync {
+            team,
+            comments,
+            full,
+        } => sync(&team, comments, full, output).await,
+        MirrorCommands::Status => status(output),
+        MirrorCommands::Clear => {
+            Mirror::clear()?;
+            if output.is_json() || output.has_template() {
+                print_json(&json!({ "cleared": true }), output)?;
+            } else {
+                println!("{} Cleared local mirror", "+".green());
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn sync(teams: &[String], comments: bool, full: bool, output: &OutputOptions) -> Result<()> {
+    let client = LinearClient::new()?;
+    let mut mirror = Mirror::load()?;
+
+    let team_ids: Vec<String> = if teams.is_empty() {
+        if mirror.teams.is_empty() {
+            return Err(CliError::new(
+                5,
+                "Nothing mirrored yet; pick the teams to mirror with --team KEY",
+            )
+            .into());
+        }
+        mirror.teams.keys().cloned().collect()
+    } else {
+        let mut ids = Vec::new();
+        for team in teams {
+            ids.push(resolve_team_id(&client, team, &output.cache).await?);
+        }
+        ids
+    };
+
+    let query = r#"
+        query($id: String!) {
+            team(id: $id) { id key name }
+        }
+    "#;
+
+    let mut results = Vec::new();
+    for team_id in team_ids {
+        let result = client.query(query, Some(json!({ "id": team_id }))).await?;
+        let team = &result["data"]["team"];
+        if team.is_null() {
+            return Err(CliError::new(2, format!("Team not found: {}", team_id)).into());
+        }
+        let fetched = mirror::sync_team(&client, &mut mirror, team, comments, full).await?;
+        // Save per team so an interrupted sync keeps the teams already done
+        mirror.save()?;
+        let key = team["key"].as_str().unwrap_or_default().to_string();
+        if !output.is_json() && !output.has_template() {
+            println!(
+                "{} {}: {} updated, {} mirrored",
+                "+".green(),
+                key,
+                fetched,
+                mirror.count_for_team(&team_id)
+            );
+        }
+        results.push(json!({
+            "team": key,
+            "fetched": fetched,
+            "issues": mirror.count_for_team(&team_id),
+        }));
+    }
+
+    if output.is_json() || output.has_template() {
+        print_json(&json!({ "teams": results }), output)?;
+    }
+    Ok(())
+}
+
+fn status(output: &OutputOptions) -> Result<()> {
+    let mirror = Mirror::load()?;
+
+    if output.is_json() || output.has_template() {
+        let teams: Vec<Value> = mirror
+            .teams
+            .iter()
+            .map(|(id, t)| {
+                json!({
+                    "id": id,
+                    "key": t.key,
+                    "name": t.name,
+                    "issues": mirror.count_for_team(id),
+                    "comments": t.comments,
+                    "synced_at": t.synced_at,
+                    "updated_after": t.updated_after,
+                })
+            })
+            .collect();
+        print_json(&json!({ "teams": teams }), output)?;
+        return Ok(());
+    }
+
+    if mirror.teams.is_empty() {
+        println!("No local mirror. Run `linear mirror sync --team KEY` to create one.");
+        return Ok(());
+    }
+
+    let rows: Vec<MirrorRow> = mirror
+        .teams
+        .iter()
+        .map(|(id, t)| MirrorRow {
+            team: format!("{} ({})", t.key, t.name),
+            issues: mirror.count_for_team(id),
+            comments: if t.comments { "Yes" } else { "No" }.to_string(),
+            synced_at: t
+                .synced_at
+                .chars()
+                .take(19)
+                .collect::<String>()
+                .replace('T', " "),
+        })
+        .collect();
+    println!("{}", Table::new(rows));
+    println!("\n{} issues mirrored", mirror.issues.len());
+    Ok(())
+}
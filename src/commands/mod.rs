@@ -9,16 +9,21 @@ pub mod documents;
 pub mod export;
 pub mod favorites;
 pub mod git;
+pub mod graphql;
 pub mod history;
 pub mod initiatives;
 pub mod interactive;
 pub mod issues;
 pub mod labels;
+pub mod mcp;
 pub mod metrics;
+pub mod mirror;
 pub mod notifications;
+pub mod outbox;
 pub mod projects;
 pub mod relations;
 pub mod roadmaps;
+pub mod schema;
 pub mod search;
 pub mod statuses;
 pub mod sync;
@@ -0,0 +1,838 @@
+//! Offline outbox for write commands.
+//!
+//! `issues create/update`, `comments create` and `bulk` describe their work
+//! as an [`Operation`]. Normally it runs straight away; when the API can't be
+//! reached (or `--offline` is set) it is appended to a per-profile outbox in
+//! the cache directory instead, and `linear outbox flush` replays it later
+//! through the same code path. Team, user and label names are swapped for
+//! IDs from the local cache when queuing, so a queued write doesn't depend on
+//! names that may change; anything not cached is resolved at flush time, as
+//! are labels whose team isn't known yet.
+//! Creates get their client-side ID when queued, so a flush that is
+//! interrupted and run again can't create the same issue twice.
+
+use anyhow::Result;
+use chrono::Utc;
+use clap::Subcommand;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tabled::{Table, Tabled};
+
+use crate::api::{
+    ensure_client_id, find_label_id, find_team_id, find_user_id, labels_for_team, LinearClient,
+};
+use crate::cache::{self, Cache, CacheType};
+use crate::display_options;
+use crate::error::CliError;
+use crate::output::{print_json, OutputOptions};
+use crate::retry::{ErrorClass, IsRetryable};
+use crate::text::{is_uuid, new_uuid, truncate};
+use crate::AgentOptions;
+
+use super::bulk::{self, BulkCommands};
+use super::{comments, issues};
+
+const OUTBOX_FILE: &str = "outbox.json";
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Queue writes without trying the API (`--offline`)
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::SeqCst);
+}
+
+fn offline_mode() -> bool {
+    OFFLINE.load(Ordering::SeqCst)
+}
+
+#[derive(Subcommand)]
+pub enum OutboxCommands {
+    /// List queued writes
+    #[command(alias = "ls")]
+    List,
+    /// Replay queued writes in order, removing each one that succeeds
+    Flush {
+        /// Only replay these entries (default: all)
+        ids: Vec<String>,
+    },
+    /// Discard queued writes without sending them
+    #[command(alias = "rm")]
+    Drop {
+        /// Entries to discard
+        #[arg(required_unless_present = "all")]
+        ids: Vec<String>,
+        /// Discard every queued write
+        #[arg(long, conflicts_with = "ids")]
+        all: bool,
+    },
+}
+
+/// Arguments of `issues create` after templates and stdin are applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueCreate {
+    pub title: String,
+    pub team: String,
+    pub data: Option<Value>,
+    pub description: Option<String>,
+    pub priority: Option<i32>,
+    pub state: Option<String>,
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub project: Option<String>,
+    #[serde(default)]
+    pub cycle: Option<String>,
+    pub estimate: Option<i32>,
+    pub due: Option<String>,
+    pub parent: Option<String>,
+}
+
+/// Arguments of `issues update` after stdin is read
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueUpdate {
+    pub id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub data: Option<Value>,
+    pub priority: Option<i32>,
+    pub state: Option<String>,
+    pub assignee: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub project: Option<String>,
+    pub estimate: Option<i32>,
+    pub due: Option<String>,
+    pub parent: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentCreate {
+    pub issue_id: String,
+    pub body: String,
+    pub parent_id: Option<String>,
+    /// Client-side comment ID
+    pub id: Option<String>,
+}
+
+/// A write command that can run now or be queued for later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Operation {
+    IssueCreate(IssueCreate),
+    IssueUpdate(IssueUpdate),
+    CommentCreate(CommentCreate),
+    Bulk { command: BulkCommands },
+}
+
+impl Operation {
+    /// Short command-line style description for `outbox list`
+    pub fn summary(&self) -> String {
+        match self {
+            Operation::IssueCreate(c) => format!("issues create \"{}\"", c.title),
+            Operation::IssueUpdate(u) => format!("issues update {}", u.id),
+            Operation::CommentCreate(c) => format!("comments create {}", c.issue_id),
+            Operation::Bulk { command } => format!("bulk {}", bulk::describe(command)),
+        }
+    }
+
+    /// Assign client-side IDs to anything this operation creates, and read
+    /// bulk payloads from stdin/files so the queued entry is self-contained
+    fn prepare(&mut self) -> Result<()> {
+        match self {
+            Operation::IssueCreate(c) => {
+                let data = c.data.get_or_insert_with(|| json!({}));
+                if data.is_object() {
+                    ensure_client_id(data);
+                }
+            }
+            Operation::CommentCreate(c) => {
+                c.id.get_or_insert_with(new_uuid);
+            }
+            Operation::Bulk {
+                command: BulkCommands::Create { data },
+            } => {
+                let mut issues = bulk::parse_batch_create_issues(data)?;
+                for issue in &mut issues {
+                    ensure_client_id(issue);
+                }
+                *data = serde_json::to_string(&issues)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Replace names with IDs from the cache, even if it is stale
+    fn resolve_cached(&mut self) {
+        let Ok(cache) = Cache::new() else { return };
+        let cached = |cache_type: CacheType| {
+            cache
+                .get_entry(cache_type)
+                .and_then(|entry| entry.data.as_array().cloned())
+                .unwrap_or_default()
+        };
+        let teams = cached(CacheType::Teams);
+        let users = cached(CacheType::Users);
+        let labels = cached(CacheType::Labels);
+
+        let user = |name: &mut String| {
+            if let Some(id) = find_user_id(&users, name) {
+                *name = id;
+            }
+        };
+
+        match self {
+            Operation::IssueCreate(c) => {
+                if let Some(id) = find_team_id(&teams, &c.team) {
+                    c.team = id;
+                }
+                if let Some(assignee) = c.assignee.as_mut() {
+                    user(assignee);
+                }
+                // Label names are only unique per team, so they need its ID
+                if is_uuid(&c.team) {
+                    let labels = labels_for_team(&labels, &c.team);
+                    for name in c.labels.iter_mut() {
+                        if let Some(id) = find_label_id(&labels, name) {
+                            *name = id;
+                        }
+                    }
+                }
+            }
+            Operation::IssueUpdate(u) => {
+                if let Some(assignee) = u.assignee.as_mut() {
+                    user(assignee);
+                }
+            }
+            Operation::Bulk { command } => {
+                if let BulkCommands::Assign { user: name, .. } = command {
+                    user(name);
+                }
+            }
+            Operation::CommentCreate(_) => {}
+        }
+    }
+
+    /// IDs assigned by [`Operation::prepare`] to what this operation creates
+    fn client_ids(&self) -> Vec<String> {
+        match self {
+            Operation::IssueCreate(c) => c
+                .data
+                .as_ref()
+                .and_then(|data| data["id"].as_str())
+                .map(String::from)
+                .into_iter()
+                .collect(),
+            Operation::CommentCreate(c) => c.id.clone().into_iter().collect(),
+            Operation::Bulk {
+                command: BulkCommands::Create { data },
+            } => serde_json::from_str::<Vec<Value>>(data)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|issue| issue["id"].as_str().map(String::from))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// One queued write
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub id: String,
+    pub queued_at: String,
+    pub operation: Operation,
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Set while a flush is replaying the entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claim: Option<Claim>,
+}
+
+/// Marks an entry as taken by one `outbox flush`, so a concurrent flush
+/// doesn't replay it too
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claim {
+    /// Random token of the claiming flush
+    pub token: String,
+    /// Renewed after every entry the flush settles
+    pub at: String,
+}
+
+/// A claim not renewed for this long belongs to a flush that died
+const CLAIM_TIMEOUT_SECONDS: i64 = 600;
+
+impl Claim {
+    fn is_active(&self, now: chrono::DateTime<Utc>) -> bool {
+        chrono::DateTime::parse_from_rfc3339(&self.at)
+            .is_ok_and(|at| (now - at.with_timezone(&Utc)).num_seconds() < CLAIM_TIMEOUT_SECONDS)
+    }
+}
+
+fn outbox_path() -> Result<PathBuf> {
+    Ok(cache::cache_dir_path()?.join(OUTBOX_FILE))
+}
+
+fn load() -> Result<Vec<OutboxEntry>> {
+    let path = outbox_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    serde_json::from_str(&content).map_err(|e| {
+        anyhow::anyhow!(
+            "Outbox file {} is corrupt ({}); fix or remove it",
+            path.display(),
+            e
+        )
+    })
+}
+
+fn save(entries: &[OutboxEntry]) -> Result<()> {
+    cache::write_atomic(
+        &outbox_path()?,
+        serde_json::to_string_pretty(entries)?.as_bytes(),
+    )
+}
+
+/// Read-modify-write the outbox under its lock. Every change goes through
+/// here, so commands queuing or flushing at the same time never overwrite
+/// each other's entries.
+fn update<T>(modify: impl FnOnce(&mut Vec<OutboxEntry>) -> Result<T>) -> Result<T> {
+    let _lock = cache::lock(&outbox_path()?)?;
+    let mut entries = load()?;
+    let result = modify(&mut entries)?;
+    save(&entries)?;
+    Ok(result)
+}
+
+fn enqueue(mut operation: Operation) -> Result<OutboxEntry> {
+    operation.resolve_cached();
+    update(|entries| {
+        let entry = OutboxEntry {
+            id: next_id(entries),
+            queued_at: Utc::now().to_rfc3339(),
+            operation,
+            attempts: 0,
+            last_error: None,
+            claim: None,
+        };
+        entries.push(entry.clone());
+        Ok(entry)
+    })
+}
+
+/// Entry IDs are the queue time in base-36 milliseconds, bumped past the
+/// newest entry, so ordering by ID (as JSON output does) is queue order
+fn next_id(entries: &[OutboxEntry]) -> String {
+    const DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+    let newest = entries
+        .iter()
+        .filter_map(|e| u64::from_str_radix(&e.id, 36).ok())
+        .max();
+    let mut millis = u64::try_from(Utc::now().timestamp_millis()).unwrap_or(0);
+    if let Some(newest) = newest {
+        millis = millis.max(newest + 1);
+    }
+    let mut id = Vec::new();
+    while millis > 0 || id.len() < 8 {
+        id.push(DIGITS[(millis % 36) as usize]);
+        millis /= 36;
+    }
+    id.reverse();
+    String::from_utf8(id).unwrap_or_default()
+}
+
+/// Claim the selected entries that no other flush holds. Returns their IDs
+/// and operations in queue order, and how many were held elsewhere.
+fn claim(ids: &[String], token: &str) -> Result<(Vec<(String, Operation)>, usize)> {
+    update(|entries| {
+        unknown_ids(entries, ids)?;
+        let now = Utc::now();
+        let (mut claimed, mut busy) = (Vec::new(), 0);
+        for entry in entries
+            .iter_mut()
+            .filter(|e| ids.is_empty() || ids.contains(&e.id))
+        {
+            if entry.claim.as_ref().is_some_and(|c| c.is_active(now)) {
+                busy += 1;
+                continue;
+            }
+            entry.claim = Some(Claim {
+                token: token.to_string(),
+                at: now.to_rfc3339(),
+            });
+            claimed.push((entry.id.clone(), entry.operation.clone()));
+        }
+        Ok((claimed, busy))
+    })
+}
+
+/// Record how replaying `id` went: remove it on success, otherwise count the
+/// attempt and release it. Renews the flush's other claims and returns how
+/// many entries are left.
+fn settle(id: &str, token: &str, error: Option<String>) -> Result<usize> {
+    update(|entries| {
+        match error {
+            None => entries.retain(|e| e.id != id),
+            Some(error) => {
+                if let Some(entry) = entries.iter_mut().find(|e| e.id == id) {
+                    entry.attempts += 1;
+                    entry.last_error = Some(error);
+                    entry.claim = None;
+                }
+            }
+        }
+        let now = Utc::now().to_rfc3339();
+        for claim in entries.iter_mut().filter_map(|e| e.claim.as_mut()) {
+            if claim.token == token {
+                claim.at = now.clone();
+            }
+        }
+        Ok(entries.len())
+    })
+}
+
+/// Give back every entry `token` still holds
+fn release(token: &str) -> Result<()> {
+    update(|entries| {
+        for entry in entries.iter_mut() {
+            if entry.claim.as_ref().is_some_and(|c| c.token == token) {
+                entry.claim = None;
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Whether `err` means the API couldn't be reached at all
+fn is_offline_error(err: &anyhow::Error) -> bool {
+    err.error_class() == ErrorClass::Network
+}
+
+/// Whether a replayed create conflicted because an earlier attempt landed:
+/// only if what it creates now exists under the client ID queued with it
+async fn already_applied(operation: &Operation, err: &anyhow::Error) -> bool {
+    if err.downcast_ref::<CliError>().is_none_or(|e| e.code != 6) {
+        return false;
+    }
+    let ids = operation.client_ids();
+    if ids.is_empty() {
+        return false;
+    }
+    let Ok(client) = LinearClient::new() else {
+        return false;
+    };
+    let found = match operation {
+        Operation::CommentCreate(_) => client
+            .query(
+                "query($id: String!) { comment(id: $id) { id } }",
+                Some(json!({ "id": ids[0] })),
+            )
+            .await
+            .map(|result| !result["data"]["comment"].is_null()),
+        _ => client
+            .query(
+                "query($ids: [ID!]) { issues(first: 250, filter: { id: { in: $ids } }) { nodes { id } } }",
+                Some(json!({ "ids": ids })),
+            )
+            .await
+            .map(|result| {
+                result["data"]["issues"]["nodes"]
+                    .as_array()
+                    .is_some_and(|nodes| !nodes.is_empty())
+            }),
+    };
+    found.unwrap_or(false)
+}
+
+async fn execute(
+    operation: &Operation,
+    output: &OutputOptions,
+    agent_opts: AgentOptions,
+) -> Result<()> {
+    match operation.clone() {
+        Operation::IssueCreate(c) => {
+            issues::create_issue(
+                &c.title,
+                &c.team,
+                c.data,
+                c.description,
+                c.priority,
+                c.state,
+                c.assignee,
+                c.labels,
+                c.project,
+                c.cycle,
+                c.estimate,
+                c.due,
+                c.parent,
+                output,
+                agent_opts,
+                false,
+            )
+            .await
+        }
+        Operation::IssueUpdate(u) => {
+            issues::update_issue(
+                &u.id,
+                u.title,
+                u.description,
+                u.data,
+                u.priority,
+                u.state,
+                u.assignee,
+                u.labels,
+                u.project,
+                u.estimate,
+                u.due,
+                u.parent,
+                false,
+                output,
+                agent_opts,
+            )
+            .await
+        }
+        Operation::CommentCreate(c) => {
+            comments::create_comment(&c.issue_id, &c.body, c.parent_id, c.id, output).await
+        }
+        Operation::Bulk { command } => bulk::handle(command, output).await,
+    }
+}
+
+/// Run a write command, or queue it in the outbox if the API is unreachable
+pub async fn submit(
+    mut operation: Operation,
+    output: &OutputOptions,
+    agent_opts: AgentOptions,
+) -> Result<()> {
+    // Previews never touch the outbox
+    if output.dry_run || agent_opts.dry_run {
+        return execute(&operation, output, agent_opts).await;
+    }
+    operation.prepare()?;
+
+    let reason = if offline_mode() {
+        None
+    } else {
+        match execute(&operation, output, agent_opts).await {
+            Err(e) if is_offline_error(&e) => Some(e.to_string()),
+            other => return other,
+        }
+    };
+
+    let entry = enqueue(operation)?;
+    if output.is_json() || output.has_template() {
+        print_json(
+            &json!({
+                "queued": true,
+                "outbox_id": entry.id,
+                "operation": entry.operation.summary(),
+                "reason": reason,
+            }),
+            output,
+        )?;
+    } else if agent_opts.id_only || agent_opts.quiet {
+        println!("{}", entry.id);
+    } else {
+        if let Some(reason) = reason {
+            eprintln!("{} Linear API unreachable: {}", "!".yellow(), reason);
+        }
+        println!(
+            "{} Queued {} ({})",
+            "~".yellow(),
+            entry.operation.summary(),
+            entry.id.cyan()
+        );
+        println!("  Run `linear outbox flush` once you're back online.");
+    }
+    Ok(())
+}
+
+#[derive(Tabled)]
+struct OutboxRow {
+    #[tabled(rename = "ID")]
+    id: String,
+    #[tabled(rename = "Queued")]
+    queued_at: String,
+    #[tabled(rename = "Command")]
+    command: String,
+    #[tabled(rename = "Attempts")]
+    attempts: u32,
+    #[tabled(rename = "Last Error")]
+    last_error: String,
+}
+
+pub async fn handle(
+    cmd: OutboxCommands,
+    output: &OutputOptions,
+    agent_opts: AgentOptions,
+) -> Result<()> {
+    match cmd {
+        OutboxCommands::List => list_outbox(output),
+        OutboxCommands::Flush { ids } => flush_outbox(&ids, output, agent_opts).await,
+        OutboxCommands::Drop { ids, all } => drop_entries(&ids, all, output),
+    }
+}
+
+fn list_outbox(output: &OutputOptions) -> Result<()> {
+    let entries = load()?;
+    if output.is_json() || output.has_template() {
+        print_json(&serde_json::to_value(&entries)?, output)?;
+        return Ok(());
+    }
+    if entries.is_empty() {
+        println!("Outbox is empty.");
+        return Ok(());
+    }
+
+    let width = display_options().max_width(50);
+    let rows: Vec<OutboxRow> = entries
+        .iter()
+        .map(|e| OutboxRow {
+            id: e.id.clone(),
+            queued_at: e
+                .queued_at
+                .chars()
+                .take(19)
+                .collect::<String>()
+                .replace('T', " "),
+            command: truncate(&e.operation.summary(), width),
+            attempts: e.attempts,
+            last_error: truncate(e.last_error.as_deref().unwrap_or("-"), width),
+        })
+        .collect();
+    println!("{}", Table::new(rows));
+    println!("\n{} queued", entries.len());
+    Ok(())
+}
+
+fn unknown_ids(entries: &[OutboxEntry], ids: &[String]) -> Result<()> {
+    let missing: Vec<&str> = ids
+        .iter()
+        .filter(|id| !entries.iter().any(|e| &e.id == *id))
+        .map(|id| id.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(
+            CliError::new(2, format!("Outbox entry not found: {}", missing.join(", "))).into(),
+        );
+    }
+    Ok(())
+}
+
+async fn flush_outbox(
+    ids: &[String],
+    output: &OutputOptions,
+    agent_opts: AgentOptions,
+) -> Result<()> {
+    let token = new_uuid();
+    let (claimed, busy) = claim(ids, &token)?;
+    if busy > 0 && !agent_opts.quiet {
+        eprintln!(
+            "{} {} entries are being flushed by another process",
+            "!".yellow(),
+            busy
+        );
+    }
+
+    let (mut applied, mut failed) = (0, 0);
+    for (id, operation) in &claimed {
+        if !agent_opts.quiet {
+            eprintln!(
+                "{}",
+                format!("Replaying {}: {}", id, operation.summary()).dimmed()
+            );
+        }
+
+        let mut result = execute(operation, output, agent_opts).await;
+        if let Err(e) = &result {
+            if already_applied(operation, e).await {
+                result = Ok(());
+            }
+        }
+
+        // Settle after every entry so an interrupted flush never replays
+        // what already went through
+        match result {
+            Ok(()) => {
+                settle(id, &token, None)?;
+                applied += 1;
+            }
+            Err(e) if is_offline_error(&e) => {
+                let left = settle(id, &token, Some(e.to_string()))?;
+                release(&token)?;
+                return Err(CliError::new(
+                    1,
+                    format!(
+                        "Linear API still unreachable ({}); {} entries left in the outbox",
+                        e, left
+                    ),
+                )
+                .into());
+            }
+            Err(e) => {
+                if !agent_opts.quiet {
+                    eprintln!("{} {}: {}", "x".red(), id, e);
+                }
+                settle(id, &token, Some(e.to_string()))?;
+                failed += 1;
+            }
+        }
+    }
+
+    if !agent_opts.quiet {
+        eprintln!(
+            "{} Flushed {}, {} failed, {} left in the outbox",
+            ">>".cyan(),
+            applied,
+            failed,
+            load()?.len()
+        );
+    }
+    if failed > 0 {
+        return Err(CliError::new(
+            1,
+            format!("{} outbox entries failed; see `linear outbox list`", failed),
+        )
+        .into());
+    }
+    Ok(())
+}
+
+fn drop_entries(ids: &[String], all: bool, output: &OutputOptions) -> Result<()> {
+    let (dropped, remaining) = update(|entries| {
+        let dropped: Vec<String> = if all {
+            entries.drain(..).map(|e| e.id).collect()
+        } else {
+            unknown_ids(entries, ids)?;
+            entries.retain(|e| !ids.contains(&e.id));
+            ids.to_vec()
+        };
+        Ok((dropped, entries.len()))
+    })?;
+
+    if output.is_json() || output.has_template() {
+        print_json(
+            &json!({ "dropped": dropped, "remaining": remaining }),
+            output,
+        )?;
+    } else {
+        println!(
+            "{} Dropped {} queued write(s), {} left",
+            "-".red(),
+            dropped.len(),
+            remaining
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_op() -> Operation {
+        Operation::IssueCreate(IssueCreate {
+            title: "Fix bug".to_string(),
+            team: "ENG".to_string(),
+            data: None,
+            description: None,
+            priority: Some(2),
+            state: None,
+            assignee: None,
+            labels: vec![],
+            project: None,
+            cycle: None,
+            estimate: None,
+            due: None,
+            parent: None,
+        })
+    }
+
+    #[test]
+    fn test_prepare_assigns_client_ids_once() {
+        let mut op = create_op();
+        op.prepare().unwrap();
+        let Operation::IssueCreate(ref c) = op else {
+            unreachable!()
+        };
+        let id = c.data.as_ref().unwrap()["id"].as_str().unwrap().to_string();
+        assert_eq!(id.len(), 36);
+
+        // Replays keep the ID queued with the entry
+        op.prepare().unwrap();
+        let Operation::IssueCreate(c) = op else {
+            unreachable!()
+        };
+        assert_eq!(c.data.unwrap()["id"], id);
+    }
+
+    #[test]
+    fn test_operation_round_trips_through_json() {
+        let op = Operation::Bulk {
+            command: BulkCommands::UpdateState {
+                state: "Done".to_string(),
+                issues: vec!["ENG-1".to_string(), "ENG-2".to_string()],
+            },
+        };
+        let json = serde_json::to_value(&op).unwrap();
+        assert_eq!(json["kind"], "bulk");
+        let back: Operation = serde_json::from_value(json).unwrap();
+        assert_eq!(back.summary(), "bulk update-state Done (2 issues)");
+        assert_eq!(create_op().summary(), "issues create \"Fix bug\"");
+    }
+
+    #[test]
+    fn test_ids_sort_in_queue_order() {
+        let entry = |id: String| OutboxEntry {
+            id,
+            queued_at: String::new(),
+            operation: create_op(),
+            attempts: 0,
+            last_error: None,
+            claim: None,
+        };
+        let mut entries = vec![entry("ffffffff".to_string())];
+        for _ in 0..3 {
+            entries.push(entry(next_id(&entries)));
+        }
+        let ids: Vec<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        assert_eq!(ids, sorted);
+        assert!(ids.iter().all(|id| id.len() == 8));
+        sorted.dedup();
+        assert_eq!(sorted.len(), 4);
+    }
+
+    #[test]
+    fn test_client_ids_only_for_creates() {
+        let mut create = create_op();
+        assert!(create.client_ids().is_empty());
+        create.prepare().unwrap();
+        assert_eq!(create.client_ids().len(), 1);
+
+        let update = Operation::IssueUpdate(IssueUpdate {
+            id: "ENG-1".to_string(),
+            title: None,
+            description: None,
+            data: None,
+            priority: Some(1),
+            state: None,
+            assignee: None,
+            labels: vec![],
+            project: None,
+            estimate: None,
+            due: None,
+            parent: None,
+        });
+        assert!(update.client_ids().is_empty());
+    }
+}
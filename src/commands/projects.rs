@@ -10,6 +10,7 @@ use crate::display_options;
 use crate::input::read_ids_from_stdin;
 use crate::output::{ensure_non_empty, filter_values, print_json, sort_values, OutputOptions};
 use crate::pagination::paginate_nodes;
+use crate::queries::{self, Fetched};
 use crate::text::truncate;
 
 #[derive(Subcommand)]
@@ -795,45 +796,11 @@ async fn unarchive_project_update(id: &str, output: &OutputOptions) -> Result<()
 async fn list_projects(include_archived: bool, output: &OutputOptions) -> Result<()> {
     let client = LinearClient::new()?;
 
-    // Simplified query to reduce GraphQL complexity (was exceeding 10000 limit)
-    let query = r#"
-        query($includeArchived: Boolean, $first: Int, $after: String, $last: Int, $before: String) {
-            projects(first: $first, after: $after, last: $last, before: $before, includeArchived: $includeArchived) {
-                nodes {
-                    id
-                    name
-                    state
-                    url
-                    startDate
-                    targetDate
-                }
-                pageInfo {
-                    hasNextPage
-                    endCursor
-                    hasPreviousPage
-                    startCursor
-                }
-            }
-        }
-    "#;
-
-    let mut vars = serde_json::Map::new();
-    vars.insert("includeArchived".to_string(), json!(include_archived));
-
     let pagination = output.pagination.with_default_limit(50);
-    let mut projects = paginate_nodes(
-        &client,
-        query,
-        vars,
-        &["data", "projects", "nodes"],
-        &["data", "projects", "pageInfo"],
-        &pagination,
-        50,
-    )
-    .await?;
+    let mut projects = queries::list_projects(&client, include_archived, &pagination).await?;
 
     if output.is_json() || output.has_template() {
-        print_json(&serde_json::json!(projects), output)?;
+        print_json(&queries::raw_values(&projects), output)?;
         return Ok(());
     }
 
@@ -851,11 +818,11 @@ async fn list_projects(include_archived: bool, output: &OutputOptions) -> Result
     let width = display_options().max_width(50);
     let rows: Vec<ProjectRow> = projects
         .iter()
-        .map(|p| ProjectRow {
-            name: truncate(p["name"].as_str().unwrap_or(""), width),
-            status: p["state"].as_str().unwrap_or("-").to_string(),
+        .map(|Fetched { data: p, .. }| ProjectRow {
+            name: truncate(&p.name, width),
+            status: p.state.clone().unwrap_or_else(|| "-".to_string()),
             labels: "-".to_string(),
-            id: p["id"].as_str().unwrap_or("").to_string(),
+            id: p.id.clone(),
         })
         .collect();
 
@@ -1235,39 +1202,18 @@ async fn unarchive_project_status(id: &str, output: &OutputOptions) -> Result<()
 
 async fn get_project(id: &str, output: &OutputOptions) -> Result<()> {
     let client = LinearClient::new()?;
-
-    let query = r#"
-        query($id: String!) {
-            project(id: $id) {
-                id
-                name
-                description
-                icon
-                color
-                url
-                status { name }
-                labels { nodes { id name color parent { name } } }
-            }
-        }
-    "#;
-
-    let result = client.query(query, Some(json!({ "id": id }))).await?;
-    let project = &result["data"]["project"];
-
-    if project.is_null() {
-        anyhow::bail!("Project not found: {}", id);
-    }
+    let Fetched { raw, data: project } = queries::get_project(&client, id).await?;
 
     // Handle JSON output
     if output.is_json() || output.has_template() {
-        print_json(project, output)?;
+        print_json(&raw, output)?;
         return Ok(());
     }
 
-    println!("{}", project["name"].as_str().unwrap_or("").bold());
+    println!("{}", project.name.bold());
     println!("{}", "-".repeat(40));
 
-    if let Some(desc) = project["description"].as_str() {
+    if let Some(desc) = project.description.as_deref() {
         println!(
             "Description: {}",
             desc.chars().take(100).collect::<String>()
@@ -1276,24 +1222,24 @@ async fn get_project(id: &str, output: &OutputOptions) -> Result<()> {
 
     println!(
         "Status: {}",
-        project["status"]["name"].as_str().unwrap_or("-")
+        project
+            .status
+            .as_ref()
+            .map(|s| s.name.as_str())
+            .unwrap_or("-")
     );
-    println!("Color: {}", project["color"].as_str().unwrap_or("-"));
-    println!("Icon: {}", project["icon"].as_str().unwrap_or("-"));
-    println!("URL: {}", project["url"].as_str().unwrap_or("-"));
-    println!("ID: {}", project["id"].as_str().unwrap_or("-"));
-
-    let labels = project["labels"]["nodes"].as_array();
-    if let Some(labels) = labels {
-        if !labels.is_empty() {
+    println!("Color: {}", project.color.as_deref().unwrap_or("-"));
+    println!("Icon: {}", project.icon.as_deref().unwrap_or("-"));
+    println!("URL: {}", project.url.as_deref().unwrap_or("-"));
+    println!("ID: {}", project.id);
+
+    if let Some(labels) = &project.labels {
+        if !labels.nodes.is_empty() {
             println!("\nLabels:");
-            for label in labels {
-                let parent = label["parent"]["name"].as_str().unwrap_or("");
-                let name = label["name"].as_str().unwrap_or("");
-                if parent.is_empty() {
-                    println!("  - {}", name);
-                } else {
-                    println!("  - {} > {}", parent.dimmed(), name);
+            for label in &labels.nodes {
+                match &label.parent {
+                    Some(parent) => println!("  - {} > {}", parent.name.dimmed(), label.name),
+                    None => println!("  - {}", label.name),
                 }
             }
         }
@@ -1309,44 +1255,12 @@ async fn get_projects(ids: &[String], output: &OutputOptions) -> Result<()> {
 
     let client = LinearClient::new()?;
 
-    let futures: Vec<_> = ids
-        .iter()
-        .map(|id| {
-            let client = client.clone();
-            let id = id.clone();
-            async move {
-                let query = r#"
-                    query($id: String!) {
-                        project(id: $id) {
-                            id
-                            name
-                            description
-                            status { name }
-                            url
-                        }
-                    }
-                "#;
-                let result = client.query(query, Some(json!({ "id": id }))).await;
-                (id, result)
-            }
-        })
-        .collect();
-
-    let results = futures::future::join_all(futures).await;
+    let results = queries::get_projects(&client, ids).await?;
 
     if output.is_json() || output.has_template() {
         let projects: Vec<_> = results
             .iter()
-            .filter_map(|(_, r)| {
-                r.as_ref().ok().and_then(|data| {
-                    let project = &data["data"]["project"];
-                    if !project.is_null() {
-                        Some(project.clone())
-                    } else {
-                        None
-                    }
-                })
-            })
+            .filter_map(|(_, r)| r.as_ref().ok().map(|project| project.raw.clone()))
             .collect();
         print_json(&serde_json::json!(projects), output)?;
         return Ok(());
@@ -1355,15 +1269,17 @@ async fn get_projects(ids: &[String], output: &OutputOptions) -> Result<()> {
     let width = display_options().max_width(50);
     for (id, result) in results {
         match result {
-            Ok(data) => {
-                let project = &data["data"]["project"];
-                if project.is_null() {
-                    eprintln!("{} Project not found: {}", "!".yellow(), id);
-                } else {
-                    let name = truncate(project["name"].as_str().unwrap_or("-"), width);
-                    let status = project["status"]["name"].as_str().unwrap_or("-");
-                    println!("{} [{}] {}", name.cyan(), status, id);
-                }
+            Ok(Fetched { data: project, .. }) => {
+                let name = truncate(&project.name, width);
+                let status = project
+                    .status
+                    .as_ref()
+                    .map(|s| s.name.as_str())
+                    .unwrap_or("-");
+                println!("{} [{}] {}", name.cyan(), status, id);
+            }
+            Err(e) if queries::is_not_found(&e) => {
+                eprintln!("{} Project not found: {}", "!".yellow(), id);
             }
             Err(e) => {
                 eprintln!("{} Error fetching {}: {}", "!".red(), id, e);
@@ -0,0 +1,298 @@
+use anyhow::{Context, Result};
+use clap::Subcommand;
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::api::LinearClient;
+use crate::cache;
+use crate::error::CliError;
+use crate::introspection::{self, Schema};
+use crate::output::{print_json, OutputOptions};
+
+const SCHEMA_FILE: &str = "schema.json";
+
+#[derive(Subcommand)]
+pub enum SchemaCommands {
+    /// Download the GraphQL schema via introspection and cache it
+    #[command(after_help = r#"EXAMPLES:
+    linear schema fetch                     # Cache schema for current profile
+    linear --profile work schema fetch      # Cache schema for another profile"#)]
+    Fetch,
+    /// Show a type's fields, input fields or enum values
+    #[command(after_help = r#"EXAMPLES:
+    linear schema show Issue                # Fields and arguments of Issue
+    linear schema show IssueCreateInput     # Input fields
+    linear schema show Issue --output json  # Raw introspection entry"#)]
+    Show {
+        /// Type name (case-insensitive)
+        type_name: String,
+    },
+    /// Validate GraphQL documents against the cached schema
+    #[command(after_help = r#"EXAMPLES:
+    linear schema validate                  # Check queries embedded in ./src
+    linear schema validate src/commands/issues.rs
+    linear schema validate queries/ --schema-file schema.json
+
+Rust files are scanned for raw string literals that start with query, mutation,
+fragment or an anonymous selection; queries built with format! are skipped.
+.graphql and .gql files are validated whole."#)]
+    Validate {
+        /// Files or directories to check (default: ./src)
+        paths: Vec<PathBuf>,
+        /// Introspection JSON to validate against instead of the cached schema
+        #[arg(long, value_name = "FILE")]
+        schema_file: Option<PathBuf>,
+    },
+}
+
+pub async fn handle(cmd: SchemaCommands, output: &OutputOptions) -> Result<()> {
+    match cmd {
+        SchemaCommands::Fetch => fetch_schema(output).await,
+        SchemaCommands::Show { type_name } => show_type(&type_name, output),
+        SchemaCommands::Validate { paths, schema_file } => {
+            validate_paths(&paths, schema_file.as_deref(), output)
+        }
+    }
+}
+
+/// Path of the cached introspection result for the current profile
+pub fn schema_path() -> Result<PathBuf> {
+    Ok(cache::cache_dir_path()?.join(SCHEMA_FILE))
+}
+
+fn load_schema(path: Option<&Path>) -> Result<Value> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => schema_path()?,
+    };
+    if !path.exists() {
+        return Err(CliError::new(
+            1,
+            format!(
+                "No schema at {}. Run 'linear schema fetch' first.",
+                path.display()
+            ),
+        )
+        .into());
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read schema {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid schema {}", path.display()))
+}
+
+async fn fetch_schema(output: &OutputOptions) -> Result<()> {
+    let client = LinearClient::new()?;
+    let result = client
+        .query(introspection::INTROSPECTION_QUERY, None)
+        .await?;
+    let introspection = json!({ "data": result["data"] });
+    let schema = Schema::from_introspection(&introspection)?;
+
+    let path = schema_path()?;
+    cache::write_atomic(&path, serde_json::to_string(&introspection)?.as_bytes())?;
+
+    if output.is_json() || output.has_template() {
+        print_json(
+            &json!({
+                "path": path.to_string_lossy(),
+                "types": schema.types.len(),
+            }),
+            output,
+        )?;
+        return Ok(());
+    }
+
+    println!(
+        "{} Cached schema with {} types to {}",
+        "+".green(),
+        schema.types.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+fn show_type(type_name: &str, output: &OutputOptions) -> Result<()> {
+    let introspection = load_schema(None)?;
+    let ty = introspection::find_type(&introspection, type_name)
+        .ok_or_else(|| CliError::new(2, format!("Type not found: {}", type_name)))?;
+
+    if output.is_json() || output.has_template() {
+        print_json(ty, output)?;
+        return Ok(());
+    }
+
+    println!(
+        "{} {}",
+        ty["kind"].as_str().unwrap_or("").to_lowercase().dimmed(),
+        ty["name"].as_str().unwrap_or("").cyan().bold()
+    );
+    if let Some(desc) = ty["description"].as_str().filter(|d| !d.is_empty()) {
+        println!("{}", desc);
+    }
+    println!("{}", "-".repeat(60));
+
+    for field in ty["fields"].as_array().into_iter().flatten() {
+        let args: Vec<String> = field["args"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|a| {
+                format!(
+                    "{}: {}",
+                    a["name"].as_str().unwrap_or(""),
+                    introspection::render_type(&a["type"])
+                )
+            })
+            .collect();
+        let args = if args.is_empty() {
+            String::new()
+        } else {
+            format!("({})", args.join(", "))
+        };
+        let mut line = format!(
+            "  {}{}: {}",
+            field["name"].as_str().unwrap_or(""),
+            args,
+            introspection::render_type(&field["type"])
+        );
+        if field["isDeprecated"].as_bool().unwrap_or(false) {
+            line = format!("{} {}", line, "(deprecated)".yellow());
+        }
+        println!("{}", line);
+    }
+    for field in ty["inputFields"].as_array().into_iter().flatten() {
+        println!(
+            "  {}: {}",
+            field["name"].as_str().unwrap_or(""),
+            introspection::render_type(&field["type"])
+        );
+    }
+    for value in ty["enumValues"].as_array().into_iter().flatten() {
+        println!("  {}", value["name"].as_str().unwrap_or(""));
+    }
+    let possible: Vec<&str> = ty["possibleTypes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|t| t["name"].as_str())
+        .collect();
+    if !possible.is_empty() {
+        println!("  = {}", possible.join(" | "));
+    }
+
+    Ok(())
+}
+
+/// Collect files to validate, recursing into directories in sorted order
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if path.is_dir() {
+        let mut entries: Vec<PathBuf> = fs::read_dir(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .collect();
+        entries.sort();
+        for entry in entries {
+            if entry.is_dir() || is_checkable(&entry) {
+                collect_files(&entry, files)?;
+            }
+        }
+    } else if path.exists() {
+        files.push(path.to_path_buf());
+    } else {
+        return Err(CliError::new(1, format!("No such file: {}", path.display())).into());
+    }
+    Ok(())
+}
+
+fn is_checkable(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("rs") | Some("graphql") | Some("gql")
+    )
+}
+
+fn validate_paths(
+    paths: &[PathBuf],
+    schema_file: Option<&Path>,
+    output: &OutputOptions,
+) -> Result<()> {
+    let schema = Schema::from_introspection(&load_schema(schema_file)?)?;
+
+    let paths = if paths.is_empty() {
+        vec![PathBuf::from("src")]
+    } else {
+        paths.to_vec()
+    };
+    let mut files = Vec::new();
+    for path in &paths {
+        collect_files(path, &mut files)?;
+    }
+
+    let mut documents = 0;
+    let mut skipped = 0;
+    let mut errors = Vec::new();
+    for file in &files {
+        let content = fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let docs = if file.extension().and_then(|e| e.to_str()) == Some("rs") {
+            introspection::extract_documents(&content)
+        } else {
+            vec![introspection::EmbeddedDocument {
+                line: 1,
+                text: content,
+                templated: false,
+            }]
+        };
+
+        for doc in docs {
+            if doc.templated {
+                skipped += 1;
+                continue;
+            }
+            documents += 1;
+            for error in introspection::validate(&schema, &doc.text) {
+                errors.push(json!({
+                    "file": file.to_string_lossy(),
+                    "line": doc.line + error.line - 1,
+                    "message": error.message,
+                }));
+            }
+        }
+    }
+
+    if output.is_json() || output.has_template() {
+        print_json(
+            &json!({
+                "files": files.len(),
+                "documents": documents,
+                "skipped_templated": skipped,
+                "errors": errors,
+            }),
+            output,
+        )?;
+    } else {
+        for error in &errors {
+            println!(
+                "{}:{}: {}",
+                error["file"].as_str().unwrap_or(""),
+                error["line"],
+                error["message"].as_str().unwrap_or("").red()
+            );
+        }
+        println!(
+            "Checked {} documents in {} files ({} templated skipped)",
+            documents,
+            files.len(),
+            skipped
+        );
+    }
+
+    if !errors.is_empty() {
+        return Err(
+            CliError::new(1, format!("{} GraphQL validation error(s)", errors.len())).into(),
+        );
+    }
+    Ok(())
+}
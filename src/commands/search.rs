@@ -1,10 +1,11 @@
 use anyhow::Result;
 use clap::Subcommand;
-use serde_json::json;
+use serde_json::{json, Value};
 use tabled::{Table, Tabled};
 
 use crate::api::{resolve_team_id, LinearClient};
 use crate::display_options;
+use crate::mirror::Mirror;
 use crate::output::{ensure_non_empty, filter_values, print_json, sort_values, OutputOptions};
 use crate::pagination::paginate_nodes;
 use crate::text::truncate;
@@ -18,6 +19,9 @@ pub enum SearchCommands {
         /// Include archived issues
         #[arg(short, long)]
         archived: bool,
+        /// Search the local mirror instead of the API (see: linear mirror)
+        #[arg(long)]
+        local: bool,
     },
     /// Search projects by query string
     Projects {
@@ -111,7 +115,18 @@ struct SemanticRow {
 
 pub async fn handle(cmd: SearchCommands, output: &OutputOptions) -> Result<()> {
     match cmd {
-        SearchCommands::Issues { query, archived } => search_issues(&query, archived, output).await,
+        SearchCommands::Issues {
+            query,
+            archived,
+            local,
+        } => {
+            let issues = if local {
+                local_search_issues(&query, archived, output)?
+            } else {
+                search_issues(&query, archived, output).await?
+            };
+            print_issues(&query, issues, output)
+        }
         SearchCommands::Projects { query, archived } => {
             search_projects(&query, archived, output).await
         }
@@ -130,7 +145,11 @@ pub async fn handle(cmd: SearchCommands, output: &OutputOptions) -> Result<()> {
     }
 }
 
-async fn search_issues(query: &str, include_archived: bool, output: &OutputOptions) -> Result<()> {
+async fn search_issues(
+    query: &str,
+    include_archived: bool,
+    output: &OutputOptions,
+) -> Result<Vec<Value>> {
     let client = LinearClient::new()?;
 
     let graphql_query = r#"
@@ -166,7 +185,7 @@ async fn search_issues(query: &str, include_archived: bool, output: &OutputOptio
     );
 
     let pagination = output.pagination.with_default_limit(50);
-    let mut issues = paginate_nodes(
+    paginate_nodes(
         &client,
         graphql_query,
         variables,
@@ -175,8 +194,30 @@ async fn search_issues(query: &str, include_archived: bool, output: &OutputOptio
         &pagination,
         50,
     )
-    .await?;
+    .await
+}
+
+/// Mirrored issues whose title or description contains `query`
+fn local_search_issues(
+    query: &str,
+    include_archived: bool,
+    output: &OutputOptions,
+) -> Result<Vec<Value>> {
+    let needle = query.to_lowercase();
+    let contains = |text: &Value| {
+        text.as_str()
+            .is_some_and(|t| t.to_lowercase().contains(&needle))
+    };
+    let pagination = output.pagination.with_default_limit(50);
+    Ok(Mirror::load_synced()?
+        .issues(include_archived)
+        .into_iter()
+        .filter(|issue| contains(&issue["title"]) || contains(&issue["description"]))
+        .take(pagination.limit.unwrap_or(usize::MAX))
+        .collect())
+}
 
+fn print_issues(query: &str, mut issues: Vec<Value>, output: &OutputOptions) -> Result<()> {
     if output.is_json() || output.has_template() {
         print_json(&serde_json::json!(issues), output)?;
         return Ok(());
@@ -8,8 +8,11 @@ use std::path::Path;
 
 use crate::api::{resolve_team_id, LinearClient};
 use crate::cache::{Cache, CacheType};
+use crate::config;
 use crate::display_options;
+use crate::error::CliError;
 use crate::output::{print_json, OutputOptions};
+use crate::project_config;
 use crate::text::truncate;
 
 /// Get default directory to scan for local projects (cross-platform)
@@ -38,14 +41,15 @@ pub enum SyncCommands {
     #[command(after_help = r#"EXAMPLES:
     linear sync push -t ENG                    # Create projects for all folders
     linear sy push -t ENG --dry-run            # Preview without creating
-    linear sy push -t ENG -o proj1,proj2       # Only specific folders"#)]
+    linear sy push -t ENG -o proj1,proj2       # Only specific folders
+    linear sy push                             # Team from .linear.toml"#)]
     Push {
         /// Directory to scan for local projects (default: ~/code)
         #[arg(short, long)]
         directory: Option<String>,
-        /// Team name or ID to create projects in
+        /// Team name or ID to create projects in (default: team from .linear.toml)
         #[arg(short, long)]
-        team: String,
+        team: Option<String>,
         /// Only push specific folders (comma-separated)
         #[arg(short, long)]
         only: Option<String>,
@@ -100,6 +104,15 @@ pub async fn handle(cmd: SyncCommands, output: &OutputOptions) -> Result<()> {
             dry_run,
         } => {
             let dry_run = dry_run || output.dry_run;
+            let team = team.or_else(config::default_team).ok_or_else(|| {
+                CliError::new(
+                    5,
+                    format!(
+                        "--team is required (or set team in {} or the profile)",
+                        project_config::FILE_NAME
+                    ),
+                )
+            })?;
             push_command(directory, team, only, dry_run, &output.cache).await
         }
     }
@@ -146,7 +159,7 @@ fn scan_local_projects(dir: &str) -> Result<Vec<LocalProject>> {
     }
 
     // Sort alphabetically
-    projects.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    projects.sort_by_key(|a| a.name.to_lowercase());
 
     Ok(projects)
 }
@@ -5,11 +5,13 @@ use serde_json::{json, Value};
 use tabled::{Table, Tabled};
 
 use crate::api::{resolve_team_id, LinearClient};
-use crate::cache::{Cache, CacheType};
+use crate::batch::{self, BatchQuery};
+use crate::cache::{self, Cache, CacheType};
 use crate::display_options;
 use crate::input::read_ids_from_stdin;
 use crate::output::{ensure_non_empty, filter_values, print_json, sort_values, OutputOptions};
 use crate::pagination::paginate_nodes;
+use crate::queries;
 use crate::text::truncate;
 
 #[derive(Subcommand)]
@@ -46,13 +48,17 @@ pub enum TeamCommands {
     /// Update an existing team
     #[command(after_help = r##"EXAMPLES:
     linear teams update ENG -n "Engineering"
-    linear t update ENG -d "Updated description" -c "#10B981""##)]
+    linear t update ENG -d "Updated description" -c "#10B981"
+    linear t update ENG --key PLAT                 # Change the team key"##)]
     Update {
         /// Team ID, key, or name
         id: String,
         /// New team name
         #[arg(short, long)]
         name: Option<String>,
+        /// New team key (renames issue identifiers)
+        #[arg(short, long)]
+        key: Option<String>,
         /// New description
         #[arg(short, long)]
         description: Option<String>,
@@ -95,10 +101,11 @@ pub async fn handle(cmd: TeamCommands, output: &OutputOptions) -> Result<()> {
         TeamCommands::Update {
             id,
             name,
+            key,
             description,
             color_hex,
             icon,
-        } => update_team(&id, name, description, color_hex, icon, output).await,
+        } => update_team(&id, name, key, description, color_hex, icon, output).await,
     }
 }
 
@@ -197,36 +204,39 @@ async fn list_teams(output: &OutputOptions) -> Result<()> {
     Ok(())
 }
 
-async fn get_team(id: &str, output: &OutputOptions) -> Result<()> {
-    let client = LinearClient::new()?;
+const TEAM_DETAIL: &str = r#"
+    fragment TeamDetail on Team {
+        id
+        name
+        key
+        description
+        icon
+        color
+        private
+        timezone
+        issueCount
+        createdAt
+        updatedAt
+    }
+"#;
 
-    let query = r#"
-        query($id: String!) {
-            team(id: $id) {
-                id
-                name
-                key
-                description
-                icon
-                color
-                private
-                timezone
-                issueCount
-                createdAt
-                updatedAt
-            }
-        }
-    "#;
+const TEAM_BATCH: BatchQuery = BatchQuery {
+    field: "team",
+    fragment_name: "TeamDetail",
+    fragment: TEAM_DETAIL,
+    what: "Team",
+};
 
-    let result = client.query(query, Some(json!({ "id": id }))).await?;
-    let team = &result["data"]["team"];
+async fn get_team(id: &str, output: &OutputOptions) -> Result<()> {
+    let client = LinearClient::new()?;
 
-    if team.is_null() {
-        anyhow::bail!("Team not found: {}", id);
-    }
+    let (_, result) = batch::fetch_by_ids(&client, &TEAM_BATCH, &[id.to_string()])
+        .await?
+        .remove(0);
+    let team = result?;
 
     if output.is_json() || output.has_template() {
-        print_json(team, output)?;
+        print_json(&team, output)?;
         return Ok(());
     }
 
@@ -278,44 +288,12 @@ async fn get_teams(ids: &[String], output: &OutputOptions) -> Result<()> {
     }
 
     let client = LinearClient::new()?;
-
-    let futures: Vec<_> = ids
-        .iter()
-        .map(|id| {
-            let client = client.clone();
-            let id = id.clone();
-            async move {
-                let query = r#"
-                    query($id: String!) {
-                        team(id: $id) {
-                            id
-                            name
-                            key
-                            private
-                        }
-                    }
-                "#;
-                let result = client.query(query, Some(json!({ "id": id }))).await;
-                (id, result)
-            }
-        })
-        .collect();
-
-    let results = futures::future::join_all(futures).await;
+    let results = batch::fetch_by_ids(&client, &TEAM_BATCH, ids).await?;
 
     if output.is_json() || output.has_template() {
         let teams: Vec<_> = results
             .iter()
-            .filter_map(|(_, r)| {
-                r.as_ref().ok().and_then(|data| {
-                    let team = &data["data"]["team"];
-                    if !team.is_null() {
-                        Some(team.clone())
-                    } else {
-                        None
-                    }
-                })
-            })
+            .filter_map(|(_, r)| r.as_ref().ok().cloned())
             .collect();
         print_json(&serde_json::json!(teams), output)?;
         return Ok(());
@@ -324,16 +302,14 @@ async fn get_teams(ids: &[String], output: &OutputOptions) -> Result<()> {
     let width = display_options().max_width(30);
     for (id, result) in results {
         match result {
-            Ok(data) => {
-                let team = &data["data"]["team"];
-                if team.is_null() {
-                    eprintln!("{} Team not found: {}", "!".yellow(), id);
-                } else {
-                    let name = truncate(team["name"].as_str().unwrap_or("-"), width);
-                    let key = team["key"].as_str().unwrap_or("-");
-                    let private = team["private"].as_bool().unwrap_or(false);
-                    println!("{} ({}) private={} id={}", name.cyan(), key, private, id);
-                }
+            Ok(team) => {
+                let name = truncate(team["name"].as_str().unwrap_or("-"), width);
+                let key = team["key"].as_str().unwrap_or("-");
+                let private = team["private"].as_bool().unwrap_or(false);
+                println!("{} ({}) private={} id={}", name.cyan(), key, private, id);
+            }
+            Err(e) if queries::is_not_found(&e) => {
+                eprintln!("{} Team not found: {}", "!".yellow(), id);
             }
             Err(e) => {
                 eprintln!("{} Error fetching {}: {}", "!".red(), id, e);
@@ -404,9 +380,11 @@ async fn create_team(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn update_team(
     id: &str,
     name: Option<String>,
+    key: Option<String>,
     description: Option<String>,
     color: Option<String>,
     icon: Option<String>,
@@ -419,6 +397,22 @@ async fn update_team(
     if let Some(n) = name {
         input["name"] = json!(n);
     }
+    // The old key is needed to drop cached identifiers that used it
+    let old_key = match key {
+        Some(ref k) => {
+            input["key"] = json!(k.to_uppercase());
+            let result = client
+                .query(
+                    "query($id: String!) { team(id: $id) { key } }",
+                    Some(json!({ "id": team_id })),
+                )
+                .await?;
+            result["data"]["team"]["key"]
+                .as_str()
+                .map(|k| k.to_string())
+        }
+        None => None,
+    };
     if let Some(d) = description {
         input["description"] = json!(d);
     }
@@ -450,6 +444,16 @@ async fn update_team(
     if result["data"]["teamUpdate"]["success"].as_bool() == Some(true) {
         let team = &result["data"]["teamUpdate"]["team"];
 
+        let new_key = team["key"].as_str().unwrap_or_default();
+        if let Some(old_key) = old_key.filter(|k| k != new_key) {
+            // Identifiers under either key may now mean a different issue
+            let prefixes = [format!("{}-", old_key), format!("{}-", new_key)];
+            cache::forget(CacheType::IssueIds, |identifier, _| {
+                prefixes.iter().any(|p| identifier.starts_with(p.as_str()))
+            });
+            let _ = Cache::new()?.clear_type(CacheType::Teams);
+        }
+
         if output.is_json() || output.has_template() {
             print_json(team, output)?;
             return Ok(());
@@ -127,7 +127,7 @@ fn list_templates(output: &OutputOptions) -> Result<()> {
     let store = load_templates()?;
 
     if store.templates.is_empty() {
-        ensure_non_empty(&[], output)?;
+        ensure_non_empty::<serde_json::Value>(&[], output)?;
         println!("No templates found.");
         println!("\nCreate one with: linear-cli templates create <name>");
         return Ok(());
@@ -1,11 +1,11 @@
 use anyhow::{Context, Result};
 use clap::Subcommand;
-use reqwest::Client;
 use serde_json::json;
 use std::io::{self, Write};
 use std::path::Path;
 
-use crate::api::{resolve_issue_id, LinearClient};
+use crate::api::{resolve_issue_id, upload_client, LinearClient};
+use crate::config;
 
 #[derive(Subcommand)]
 pub enum UploadCommands {
@@ -65,9 +65,11 @@ pub async fn handle(cmd: UploadCommands) -> Result<()> {
 
 async fn fetch_upload(url: &str, file: Option<String>) -> Result<()> {
     // Validate URL is a Linear upload URL
-    if !url.starts_with("https://uploads.linear.app/") {
+    let uploads_url = config::uploads_url();
+    if !is_upload_url(url, &uploads_url) {
         anyhow::bail!(
-            "Invalid URL: expected Linear upload URL starting with 'https://uploads.linear.app/'"
+            "Invalid URL: expected Linear upload URL starting with '{}'",
+            uploads_url
         );
     }
 
@@ -94,6 +96,23 @@ async fn fetch_upload(url: &str, file: Option<String>) -> Result<()> {
     Ok(())
 }
 
+/// Whether `url` is on the uploads host (same scheme, host and port) under
+/// the base path. The API key is sent along, so a plain prefix match would
+/// let `https://uploads.linear.app.example.com` through.
+fn is_upload_url(url: &str, base: &str) -> bool {
+    let (Ok(url), Ok(base)) = (reqwest::Url::parse(url), reqwest::Url::parse(base)) else {
+        return false;
+    };
+    if url.scheme() != base.scheme()
+        || url.host_str() != base.host_str()
+        || url.port_or_known_default() != base.port_or_known_default()
+    {
+        return false;
+    }
+    let prefix = base.path().trim_end_matches('/');
+    url.path() == prefix || url.path().starts_with(&format!("{}/", prefix))
+}
+
 async fn attach_url(issue: &str, url: &str, title: Option<String>) -> Result<()> {
     let client = LinearClient::new()?;
     let issue_id = resolve_issue_id(&client, issue, true).await?;
@@ -190,7 +209,7 @@ async fn upload_file(
         .ok_or_else(|| anyhow::anyhow!("Missing asset URL"))?;
 
     let mut has_content_type = false;
-    let mut req = Client::new().put(upload_url).body(data);
+    let mut req = upload_client()?.put(upload_url).body(data);
     if let Some(headers) = upload_file["headers"].as_array() {
         for header in headers {
             if let (Some(key), Some(value)) = (header["key"].as_str(), header["value"].as_str()) {
@@ -244,3 +263,36 @@ async fn upload_file(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upload_url_matches_host_not_prefix() {
+        let base = "https://uploads.linear.app";
+        assert!(is_upload_url(
+            "https://uploads.linear.app/abc/file.png",
+            base
+        ));
+        assert!(is_upload_url("https://uploads.linear.app:443/abc", base));
+        assert!(!is_upload_url(
+            "https://uploads.linear.app.evil.com/abc",
+            base
+        ));
+        assert!(!is_upload_url(
+            "https://uploads.linear.app@evil.com/abc",
+            base
+        ));
+        assert!(!is_upload_url("http://uploads.linear.app/abc", base));
+        assert!(!is_upload_url("https://uploads.linear.app:8443/abc", base));
+
+        let base = "http://127.0.0.1:8080/uploads";
+        assert!(is_upload_url("http://127.0.0.1:8080/uploads/a.txt", base));
+        assert!(!is_upload_url(
+            "http://127.0.0.1:8080/uploads-other/a.txt",
+            base
+        ));
+        assert!(!is_upload_url("not a url", base));
+    }
+}
@@ -2,6 +2,9 @@ use serde_json::Value;
 use std::error::Error;
 use std::fmt;
 
+/// Error with a process exit code:
+/// 1 general, 2 not found, 3 auth/forbidden, 4 rate limited,
+/// 5 invalid input (validation), 6 conflict (already exists)
 #[derive(Debug)]
 pub struct CliError {
     pub code: u8,
@@ -31,6 +34,61 @@ impl CliError {
     }
 }
 
+/// Exit code and message for one GraphQL error, from its `extensions`
+/// (`code`, `type`). Messages are free text and never decide the code.
+fn classify_graphql_error(error: &Value) -> (u8, &'static str) {
+    let extensions = &error["extensions"];
+    let code = extensions["code"].as_str().unwrap_or("").to_uppercase();
+    let kind = extensions["type"].as_str().unwrap_or("").to_lowercase();
+
+    if matches!(code.as_str(), "RATELIMITED" | "RATE_LIMITED") || kind == "ratelimited" {
+        (4, "Rate limit exceeded")
+    } else if matches!(code.as_str(), "AUTHENTICATION_ERROR" | "UNAUTHENTICATED")
+        || kind == "authentication error"
+    {
+        (3, "Authentication failed")
+    } else if code == "FORBIDDEN" || kind == "forbidden" {
+        (3, "Access denied")
+    } else if matches!(code.as_str(), "ENTITY_NOT_FOUND" | "NOT_FOUND") {
+        (2, "Not found")
+    } else if code == "CONFLICT" {
+        (6, "Conflict")
+    } else if matches!(
+        code.as_str(),
+        "INVALID_INPUT" | "BAD_USER_INPUT" | "GRAPHQL_VALIDATION_FAILED" | "GRAPHQL_PARSE_FAILED"
+    ) || kind == "invalid input"
+    {
+        (5, "Invalid input")
+    } else {
+        (1, "GraphQL error")
+    }
+}
+
+impl CliError {
+    /// Build an error from a GraphQL `errors` array, classified by its first
+    /// entry. `details` keeps the full array plus the failing field `path`
+    /// (e.g. `issueCreate.input.title`) and the server's extension code.
+    pub fn from_graphql_errors(errors: &Value) -> Self {
+        let first = errors.get(0).unwrap_or(&Value::Null);
+        let (code, message) = classify_graphql_error(first);
+        let path = first["path"].as_array().map(|parts| {
+            parts
+                .iter()
+                .map(|p| match p {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(".")
+        });
+        CliError::new(code, message).with_details(serde_json::json!({
+            "errors": errors,
+            "path": path,
+            "code": first["extensions"]["code"],
+        }))
+    }
+}
+
 impl fmt::Display for CliError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.message)?;
@@ -127,6 +185,65 @@ mod tests {
         assert_eq!(err.to_string(), "GraphQL error");
     }
 
+    #[test]
+    fn test_from_graphql_errors_classifies_extensions() {
+        let cases = [
+            (
+                json!({"message": "x", "extensions": {"code": "RATELIMITED"}}),
+                4,
+            ),
+            (
+                json!({"message": "x", "extensions": {"type": "authentication error"}}),
+                3,
+            ),
+            (
+                json!({"message": "x", "extensions": {"code": "FORBIDDEN"}}),
+                3,
+            ),
+            (
+                json!({"message": "Entity not found: Issue", "extensions": {"code": "ENTITY_NOT_FOUND"}}),
+                2,
+            ),
+            (
+                json!({"message": "x", "extensions": {"code": "CONFLICT"}}),
+                6,
+            ),
+            // Words in the message alone don't change the exit code
+            (json!({"message": "Issue with this id already exists"}), 1),
+            (json!({"message": "Duplicate label name"}), 1),
+            (
+                json!({"message": "Entity not found: Issue", "extensions": {"type": "invalid input"}}),
+                5,
+            ),
+            (
+                json!({"message": "Argument Validation Error", "extensions": {"code": "INVALID_INPUT"}}),
+                5,
+            ),
+            (json!({"message": "Something broke"}), 1),
+        ];
+        for (error, code) in cases {
+            let err = CliError::from_graphql_errors(&json!([error]));
+            assert_eq!(err.code, code, "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_from_graphql_errors_includes_path() {
+        let errors = json!([{
+            "message": "title must be shorter than 256 characters",
+            "path": ["issueCreate", "input", "title"],
+            "extensions": {"code": "INVALID_INPUT"}
+        }]);
+        let err = CliError::from_graphql_errors(&errors);
+        let details = err.details.as_ref().unwrap();
+        assert_eq!(details["path"], "issueCreate.input.title");
+        assert_eq!(details["code"], "INVALID_INPUT");
+        assert_eq!(
+            err.to_string(),
+            "Invalid input: title must be shorter than 256 characters"
+        );
+    }
+
     #[test]
     fn test_display_with_errors_missing_message() {
         let errors = json!([{"code": 123}, {"extensions": {}}]);
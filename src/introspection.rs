@@ -0,0 +1,887 @@
+//! GraphQL schema introspection and offline query validation.
+//!
+//! `Schema` is built from a standard introspection result. `validate` checks a
+//! GraphQL document against it: every selected field must exist on its parent
+//! type, arguments must be declared, leaf fields may not have selections and
+//! object fields must have one. It is deliberately not a full spec validator;
+//! it targets the mistakes that show up when the API renames or drops fields.
+//!
+//! `extract_documents` pulls the raw-string GraphQL documents out of Rust
+//! source so the queries embedded in `src/commands/*.rs` can be checked.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::error::CliError;
+
+pub const INTROSPECTION_QUERY: &str = r#"
+    query IntrospectionQuery {
+        __schema {
+            queryType { name }
+            mutationType { name }
+            types {
+                kind
+                name
+                description
+                fields(includeDeprecated: true) {
+                    name
+                    description
+                    isDeprecated
+                    deprecationReason
+                    args { name description type { ...TypeRef } defaultValue }
+                    type { ...TypeRef }
+                }
+                inputFields { name description type { ...TypeRef } defaultValue }
+                interfaces { name }
+                enumValues(includeDeprecated: true) { name description isDeprecated }
+                possibleTypes { name }
+            }
+        }
+    }
+
+    fragment TypeRef on __Type {
+        kind
+        name
+        ofType {
+            kind
+            name
+            ofType {
+                kind
+                name
+                ofType {
+                    kind
+                    name
+                    ofType { kind name }
+                }
+            }
+        }
+    }
+"#;
+
+#[derive(Debug)]
+pub struct FieldInfo {
+    /// Innermost named type (list and non-null wrappers removed)
+    pub type_name: String,
+    pub args: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct TypeInfo {
+    pub kind: String,
+    pub fields: HashMap<String, FieldInfo>,
+}
+
+#[derive(Debug)]
+pub struct Schema {
+    pub query_type: String,
+    pub mutation_type: Option<String>,
+    pub types: HashMap<String, TypeInfo>,
+}
+
+/// A problem found in a document; `line` is 1-based within the document
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Schema {
+    /// Build from an introspection response (`{data: {__schema}}` or `{__schema}`)
+    pub fn from_introspection(value: &Value) -> Result<Self> {
+        let schema = value
+            .get("data")
+            .unwrap_or(value)
+            .get("__schema")
+            .ok_or_else(|| CliError::new(1, "Not a GraphQL introspection result"))?;
+
+        let query_type = schema["queryType"]["name"]
+            .as_str()
+            .unwrap_or("Query")
+            .to_string();
+        let mutation_type = schema["mutationType"]["name"]
+            .as_str()
+            .map(|s| s.to_string());
+
+        let mut types = HashMap::new();
+        for ty in schema["types"].as_array().into_iter().flatten() {
+            let Some(name) = ty["name"].as_str() else {
+                continue;
+            };
+            let fields = ty["fields"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|field| {
+                    let name = field["name"].as_str()?;
+                    let info = FieldInfo {
+                        type_name: named_type(&field["type"])?.to_string(),
+                        args: field["args"]
+                            .as_array()
+                            .into_iter()
+                            .flatten()
+                            .filter_map(|a| a["name"].as_str().map(|s| s.to_string()))
+                            .collect(),
+                    };
+                    Some((name.to_string(), info))
+                })
+                .collect();
+            types.insert(
+                name.to_string(),
+                TypeInfo {
+                    kind: ty["kind"].as_str().unwrap_or("").to_string(),
+                    fields,
+                },
+            );
+        }
+
+        // Introspection meta-fields are implicit on the query root and never
+        // listed among its fields
+        if let Some(root) = types.get_mut(&query_type) {
+            root.fields.insert(
+                "__schema".to_string(),
+                FieldInfo {
+                    type_name: "__Schema".to_string(),
+                    args: Vec::new(),
+                },
+            );
+            root.fields.insert(
+                "__type".to_string(),
+                FieldInfo {
+                    type_name: "__Type".to_string(),
+                    args: vec!["name".to_string()],
+                },
+            );
+        }
+
+        Ok(Self {
+            query_type,
+            mutation_type,
+            types,
+        })
+    }
+
+    fn is_composite(&self, type_name: &str) -> bool {
+        self.types
+            .get(type_name)
+            .map(|t| matches!(t.kind.as_str(), "OBJECT" | "INTERFACE" | "UNION"))
+            .unwrap_or(false)
+    }
+}
+
+/// Innermost named type of an introspection type reference
+fn named_type(type_ref: &Value) -> Option<&str> {
+    let mut current = type_ref;
+    loop {
+        if let Some(name) = current["name"].as_str() {
+            return Some(name);
+        }
+        current = current.get("ofType")?;
+        if current.is_null() {
+            return None;
+        }
+    }
+}
+
+/// Render an introspection type reference as GraphQL syntax (e.g. `[Issue!]!`)
+pub fn render_type(type_ref: &Value) -> String {
+    match type_ref["kind"].as_str() {
+        Some("NON_NULL") => format!("{}!", render_type(&type_ref["ofType"])),
+        Some("LIST") => format!("[{}]", render_type(&type_ref["ofType"])),
+        _ => type_ref["name"].as_str().unwrap_or("?").to_string(),
+    }
+}
+
+/// Find the introspection entry for a type by name (case-insensitive fallback)
+pub fn find_type<'a>(introspection: &'a Value, name: &str) -> Option<&'a Value> {
+    let types = introspection
+        .get("data")
+        .unwrap_or(introspection)
+        .get("__schema")?
+        .get("types")?
+        .as_array()?;
+    types
+        .iter()
+        .find(|t| t["name"].as_str() == Some(name))
+        .or_else(|| {
+            types.iter().find(|t| {
+                t["name"]
+                    .as_str()
+                    .map(|n| n.eq_ignore_ascii_case(name))
+                    .unwrap_or(false)
+            })
+        })
+}
+
+// ---------------------------------------------------------------------------
+// Lexer
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Name(String),
+    Punct(char),
+    Spread,
+    Var,
+    Str,
+    Num,
+}
+
+struct Token {
+    tok: Tok,
+    line: usize,
+}
+
+fn tokenize(doc: &str) -> std::result::Result<Vec<Token>, ValidationError> {
+    let chars: Vec<char> = doc.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            c if c.is_whitespace() || c == ',' => i += 1,
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '"' => {
+                let start_line = line;
+                let block = chars[i..].starts_with(&['"', '"', '"']);
+                i += if block { 3 } else { 1 };
+                loop {
+                    if i >= chars.len() {
+                        return Err(ValidationError {
+                            line: start_line,
+                            message: "Unterminated string".to_string(),
+                        });
+                    }
+                    if chars[i] == '\n' {
+                        line += 1;
+                    }
+                    if chars[i] == '\\' {
+                        i += 2;
+                        continue;
+                    }
+                    if block && chars[i..].starts_with(&['"', '"', '"']) {
+                        i += 3;
+                        break;
+                    }
+                    if !block && chars[i] == '"' {
+                        i += 1;
+                        break;
+                    }
+                    i += 1;
+                }
+                tokens.push(Token {
+                    tok: Tok::Str,
+                    line: start_line,
+                });
+            }
+            '.' if chars[i..].starts_with(&['.', '.', '.']) => {
+                tokens.push(Token {
+                    tok: Tok::Spread,
+                    line,
+                });
+                i += 3;
+            }
+            '$' => {
+                tokens.push(Token {
+                    tok: Tok::Var,
+                    line,
+                });
+                i += 1;
+            }
+            '{' | '}' | '(' | ')' | '[' | ']' | ':' | '!' | '=' | '@' | '|' | '&' => {
+                tokens.push(Token {
+                    tok: Tok::Punct(c),
+                    line,
+                });
+                i += 1;
+            }
+            c if c == '-' || c.is_ascii_digit() => {
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '-' | '+' | '.'))
+                {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    tok: Tok::Num,
+                    line,
+                });
+            }
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let start = i;
+                while i < chars.len() && (chars[i] == '_' || chars[i].is_ascii_alphanumeric()) {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    tok: Tok::Name(chars[start..i].iter().collect()),
+                    line,
+                });
+            }
+            other => {
+                return Err(ValidationError {
+                    line,
+                    message: format!("Unexpected character '{}'", other),
+                })
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+// ---------------------------------------------------------------------------
+// Parser / validator
+
+struct Validator<'a> {
+    schema: &'a Schema,
+    tokens: Vec<Token>,
+    pos: usize,
+    errors: Vec<ValidationError>,
+}
+
+type Step<T> = std::result::Result<T, ValidationError>;
+
+impl<'a> Validator<'a> {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|t| &t.tok)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map(|t| t.line)
+            .unwrap_or(1)
+    }
+
+    fn fail<T>(&self, message: impl Into<String>) -> Step<T> {
+        Err(ValidationError {
+            line: self.line(),
+            message: message.into(),
+        })
+    }
+
+    fn error(&mut self, line: usize, message: String) {
+        self.errors.push(ValidationError { line, message });
+    }
+
+    fn next(&mut self) -> Option<Tok> {
+        let tok = self.tokens.get(self.pos).map(|t| t.tok.clone());
+        self.pos += 1;
+        tok
+    }
+
+    fn at_punct(&self, c: char) -> bool {
+        self.peek() == Some(&Tok::Punct(c))
+    }
+
+    fn expect_punct(&mut self, c: char) -> Step<()> {
+        if self.at_punct(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            self.fail(format!("Expected '{}'", c))
+        }
+    }
+
+    fn expect_name(&mut self) -> Step<String> {
+        match self.peek() {
+            Some(Tok::Name(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => self.fail("Expected a name"),
+        }
+    }
+
+    fn document(&mut self) -> Step<()> {
+        while self.peek().is_some() {
+            self.definition()?;
+        }
+        Ok(())
+    }
+
+    fn definition(&mut self) -> Step<()> {
+        let root = match self.peek() {
+            Some(Tok::Punct('{')) => Some(self.schema.query_type.clone()),
+            Some(Tok::Name(keyword)) => match keyword.as_str() {
+                "query" => Some(self.schema.query_type.clone()),
+                "mutation" => match &self.schema.mutation_type {
+                    Some(name) => Some(name.clone()),
+                    None => return self.fail("Schema has no mutation type"),
+                },
+                "fragment" => None,
+                other => return self.fail(format!("Unsupported definition '{}'", other)),
+            },
+            _ => return self.fail("Expected an operation"),
+        };
+
+        let Some(root) = root else {
+            // fragment Name on Type { ... }
+            self.pos += 1;
+            self.expect_name()?;
+            match self.next() {
+                Some(Tok::Name(on)) if on == "on" => {}
+                _ => return self.fail("Expected 'on'"),
+            }
+            let line = self.line();
+            let type_name = self.expect_name()?;
+            self.directives()?;
+            return self.typed_selection_set(&type_name, line);
+        };
+
+        if !self.at_punct('{') {
+            self.pos += 1;
+            if let Some(Tok::Name(_)) = self.peek() {
+                self.pos += 1;
+            }
+            if self.at_punct('(') {
+                self.variable_definitions()?;
+            }
+            self.directives()?;
+        }
+        self.selection_set(&root)
+    }
+
+    fn variable_definitions(&mut self) -> Step<()> {
+        self.expect_punct('(')?;
+        while !self.at_punct(')') {
+            if self.next() != Some(Tok::Var) {
+                return self.fail("Expected a variable");
+            }
+            self.expect_name()?;
+            self.expect_punct(':')?;
+            self.type_reference()?;
+            if self.at_punct('=') {
+                self.pos += 1;
+                self.value()?;
+            }
+            self.directives()?;
+            if self.peek().is_none() {
+                return self.fail("Unterminated variable definitions");
+            }
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn type_reference(&mut self) -> Step<()> {
+        if self.at_punct('[') {
+            self.pos += 1;
+            self.type_reference()?;
+            self.expect_punct(']')?;
+        } else {
+            let line = self.line();
+            let name = self.expect_name()?;
+            if !self.schema.types.contains_key(&name) {
+                self.error(line, format!("Unknown type '{}'", name));
+            }
+        }
+        if self.at_punct('!') {
+            self.pos += 1;
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> Step<()> {
+        match self.next() {
+            Some(Tok::Var) => {
+                self.expect_name()?;
+            }
+            Some(Tok::Str) | Some(Tok::Num) | Some(Tok::Name(_)) => {}
+            Some(Tok::Punct('[')) => {
+                while !self.at_punct(']') {
+                    if self.peek().is_none() {
+                        return self.fail("Unterminated list");
+                    }
+                    self.value()?;
+                }
+                self.pos += 1;
+            }
+            Some(Tok::Punct('{')) => {
+                while !self.at_punct('}') {
+                    self.expect_name()?;
+                    self.expect_punct(':')?;
+                    self.value()?;
+                }
+                self.pos += 1;
+            }
+            _ => {
+                self.pos -= 1;
+                return self.fail("Expected a value");
+            }
+        }
+        Ok(())
+    }
+
+    fn directives(&mut self) -> Step<()> {
+        while self.at_punct('@') {
+            self.pos += 1;
+            self.expect_name()?;
+            if self.at_punct('(') {
+                self.arguments(None)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse `(name: value ...)`, checking names against `declared` if given
+    fn arguments(&mut self, declared: Option<(&str, &[String])>) -> Step<()> {
+        self.expect_punct('(')?;
+        while !self.at_punct(')') {
+            let line = self.line();
+            let name = self.expect_name()?;
+            if let Some((field, args)) = declared {
+                if !args.iter().any(|a| a == &name) {
+                    self.error(
+                        line,
+                        format!("Unknown argument '{}' on field '{}'", name, field),
+                    );
+                }
+            }
+            self.expect_punct(':')?;
+            self.value()?;
+            if self.peek().is_none() {
+                return self.fail("Unterminated arguments");
+            }
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn typed_selection_set(&mut self, type_name: &str, line: usize) -> Step<()> {
+        if self.schema.is_composite(type_name) {
+            self.selection_set(type_name)
+        } else {
+            self.error(line, format!("Unknown type '{}'", type_name));
+            self.skip_selection_set()
+        }
+    }
+
+    fn skip_selection_set(&mut self) -> Step<()> {
+        self.expect_punct('{')?;
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next() {
+                Some(Tok::Punct('{')) => depth += 1,
+                Some(Tok::Punct('}')) => depth -= 1,
+                None => return self.fail("Unterminated selection set"),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn selection_set(&mut self, parent: &str) -> Step<()> {
+        self.expect_punct('{')?;
+        while !self.at_punct('}') {
+            match self.peek() {
+                None => return self.fail("Unterminated selection set"),
+                Some(Tok::Spread) => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(Tok::Name(on)) if on == "on" => {
+                            self.pos += 1;
+                            let line = self.line();
+                            let type_name = self.expect_name()?;
+                            self.directives()?;
+                            self.typed_selection_set(&type_name, line)?;
+                        }
+                        Some(Tok::Name(_)) => {
+                            // Named fragment spread; the fragment is checked where defined
+                            self.pos += 1;
+                            self.directives()?;
+                        }
+                        _ => {
+                            self.directives()?;
+                            self.selection_set(parent)?;
+                        }
+                    }
+                }
+                _ => self.field(parent)?,
+            }
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn field(&mut self, parent: &str) -> Step<()> {
+        let line = self.line();
+        let mut name = self.expect_name()?;
+        if self.at_punct(':') {
+            self.pos += 1;
+            name = self.expect_name()?;
+        }
+
+        if name == "__typename" {
+            self.directives()?;
+            return Ok(());
+        }
+
+        let schema = self.schema;
+        let field = schema.types.get(parent).and_then(|t| t.fields.get(&name));
+        if field.is_none() {
+            self.error(
+                line,
+                format!("Cannot query field '{}' on type '{}'", name, parent),
+            );
+        }
+
+        if self.at_punct('(') {
+            self.arguments(field.map(|f| (name.as_str(), f.args.as_slice())))?;
+        }
+        self.directives()?;
+
+        let has_selection = self.at_punct('{');
+        match field {
+            None => {
+                if has_selection {
+                    self.skip_selection_set()?;
+                }
+            }
+            Some(field) if schema.is_composite(&field.type_name) => {
+                if has_selection {
+                    self.selection_set(&field.type_name)?;
+                } else {
+                    self.error(
+                        line,
+                        format!(
+                            "Field '{}' of type '{}' must have a selection of subfields",
+                            name, field.type_name
+                        ),
+                    );
+                }
+            }
+            Some(field) => {
+                if has_selection {
+                    self.error(
+                        line,
+                        format!(
+                            "Field '{}' of type '{}' cannot have a selection of subfields",
+                            name, field.type_name
+                        ),
+                    );
+                    self.skip_selection_set()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validate a GraphQL document against the schema
+pub fn validate(schema: &Schema, document: &str) -> Vec<ValidationError> {
+    let tokens = match tokenize(document) {
+        Ok(tokens) => tokens,
+        Err(e) => return vec![e],
+    };
+    let mut validator = Validator {
+        schema,
+        tokens,
+        pos: 0,
+        errors: Vec::new(),
+    };
+    if let Err(e) = validator.document() {
+        validator.errors.push(ValidationError {
+            line: e.line,
+            message: format!("Syntax error: {}", e.message),
+        });
+    }
+    validator.errors
+}
+
+// ---------------------------------------------------------------------------
+// Extraction from Rust source
+
+/// A GraphQL document found in Rust source
+#[derive(Debug, PartialEq)]
+pub struct EmbeddedDocument {
+    /// 1-based line of the opening `r#"`
+    pub line: usize,
+    pub text: String,
+    /// Built with `format!` and so not statically checkable
+    pub templated: bool,
+}
+
+/// Find `r#"..."#` literals in Rust source that look like GraphQL operations
+/// or fragments
+pub fn extract_documents(source: &str) -> Vec<EmbeddedDocument> {
+    let mut documents = Vec::new();
+    let mut rest = source;
+    let mut offset = 0;
+
+    while let Some(start) = rest.find("r#\"") {
+        let body_start = start + 3;
+        let Some(len) = rest[body_start..].find("\"#") else {
+            break;
+        };
+        let text = &rest[body_start..body_start + len];
+        let before = rest[..start].trim_end();
+
+        if looks_like_graphql(text) {
+            documents.push(EmbeddedDocument {
+                line: source[..offset + start].matches('\n').count() + 1,
+                text: text.to_string(),
+                templated: before.ends_with("format!(") || before.ends_with("format!"),
+            });
+        }
+
+        let consumed = body_start + len + 2;
+        offset += consumed;
+        rest = &rest[consumed..];
+    }
+
+    documents
+}
+
+fn looks_like_graphql(text: &str) -> bool {
+    let text = text.trim_start();
+    for keyword in ["query", "mutation", "fragment"] {
+        if let Some(after) = text.strip_prefix(keyword) {
+            return after.starts_with(|c: char| c.is_whitespace() || c == '(' || c == '{');
+        }
+    }
+    // Anonymous `{ field ... }`, but not JSON like `{"key": ...}`
+    text.strip_prefix('{')
+        .map(|after| {
+            after
+                .trim_start()
+                .starts_with(|c: char| c.is_ascii_alphabetic())
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn field(name: &str, kind: &str, type_name: &str, args: &[&str]) -> Value {
+        json!({
+            "name": name,
+            "args": args.iter().map(|a| json!({ "name": a })).collect::<Vec<_>>(),
+            "type": { "kind": "NON_NULL", "name": null, "ofType": { "kind": kind, "name": type_name } }
+        })
+    }
+
+    fn test_schema() -> Schema {
+        let introspection = json!({ "data": { "__schema": {
+            "queryType": { "name": "Query" },
+            "mutationType": { "name": "Mutation" },
+            "types": [
+                { "kind": "OBJECT", "name": "Query", "fields": [
+                    field("issue", "OBJECT", "Issue", &["id"]),
+                    field("issues", "OBJECT", "IssueConnection", &["first", "after", "filter"])
+                ] },
+                { "kind": "OBJECT", "name": "Mutation", "fields": [
+                    field("issueCreate", "OBJECT", "IssuePayload", &["input"])
+                ] },
+                { "kind": "OBJECT", "name": "Issue", "fields": [
+                    field("id", "SCALAR", "String", &[]),
+                    field("identifier", "SCALAR", "String", &[]),
+                    field("team", "OBJECT", "Team", &[])
+                ] },
+                { "kind": "OBJECT", "name": "Team", "fields": [
+                    field("key", "SCALAR", "String", &[])
+                ] },
+                { "kind": "OBJECT", "name": "IssueConnection", "fields": [
+                    field("nodes", "LIST", "Issue", &[])
+                ] },
+                { "kind": "OBJECT", "name": "IssuePayload", "fields": [
+                    field("success", "SCALAR", "Boolean", &[]),
+                    field("issue", "OBJECT", "Issue", &[])
+                ] },
+                { "kind": "SCALAR", "name": "String" },
+                { "kind": "SCALAR", "name": "Boolean" },
+                { "kind": "SCALAR", "name": "Int" },
+                { "kind": "INPUT_OBJECT", "name": "IssueCreateInput" }
+            ]
+        } } });
+        Schema::from_introspection(&introspection).unwrap()
+    }
+
+    #[test]
+    fn test_valid_query_and_mutation() {
+        let schema = test_schema();
+        let query = r#"
+            query($id: String!, $first: Int) {
+                issue(id: $id) { id identifier team { key } __typename }
+                list: issues(first: $first, filter: { team: { key: { eq: "ENG" } } }) {
+                    nodes { ... on Issue { id } }
+                }
+            }
+        "#;
+        assert_eq!(validate(&schema, query), vec![]);
+
+        let mutation =
+            "mutation($input: IssueCreateInput!) { issueCreate(input: $input) { success issue { id } } }";
+        assert_eq!(validate(&schema, mutation), vec![]);
+    }
+
+    #[test]
+    fn test_reports_unknown_field_with_line() {
+        let schema = test_schema();
+        let errors = validate(&schema, "query {\n  issue(id: \"x\") {\n    titel\n  }\n}");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 3);
+        assert!(errors[0].message.contains("'titel' on type 'Issue'"));
+    }
+
+    #[test]
+    fn test_reports_argument_and_selection_errors() {
+        let schema = test_schema();
+        let errors = validate(
+            &schema,
+            "query { issue(key: \"x\") { team id { x } } issues { nodes { id } } }",
+        );
+        let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(errors.len(), 3, "{:?}", messages);
+        assert!(messages[0].contains("Unknown argument 'key'"));
+        assert!(messages[1].contains("'team' of type 'Team' must have a selection"));
+        assert!(messages[2].contains("'id' of type 'String' cannot have a selection"));
+    }
+
+    #[test]
+    fn test_reports_syntax_error() {
+        let schema = test_schema();
+        let errors = validate(&schema, "query { issue(id: \"x\") { id }");
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.starts_with("Syntax error"));
+    }
+
+    #[test]
+    fn test_extract_documents_from_rust_source() {
+        let source = concat!(
+            "let a = r#\"query { viewer { id } }\"#;\n",
+            "let b = r#\"{\"data\": 1}\"#;\n",
+            "let c = format!(\n    r#\"\n    query {{ {field} }}\n\"#);\n",
+            "let d = r#\"EXAMPLES: linear i list\"#;\n",
+            "let e = r#\"mutation($id: String!) { x }\"#;\n",
+            "let f = r#\"fragment F on Issue { id }\"#;\n",
+        );
+        let docs = extract_documents(source);
+        assert_eq!(docs.len(), 4);
+        assert_eq!(docs[0].line, 1);
+        assert!(!docs[0].templated);
+        assert_eq!(docs[1].line, 4);
+        assert!(docs[1].templated);
+        assert_eq!(docs[2].line, 8);
+        assert_eq!(docs[3].text, "fragment F on Issue { id }");
+    }
+
+    #[test]
+    fn test_render_type() {
+        let ty = json!({ "kind": "NON_NULL", "ofType": { "kind": "LIST", "ofType": {
+            "kind": "NON_NULL", "ofType": { "kind": "OBJECT", "name": "Issue" } } } });
+        assert_eq!(render_type(&ty), "[Issue!]!");
+    }
+}
@@ -0,0 +1,315 @@
+//! Passphrase-encrypted API key storage for machines without an OS keyring.
+//!
+//! Keys live in `keys.json` next to config.toml, each sealed with
+//! ChaCha20-Poly1305 under a key derived from the passphrase with
+//! PBKDF2-HMAC-SHA256. Profile names stay readable so lookups only ask for
+//! the passphrase when the profile actually has a stored key. The passphrase
+//! comes from LINEAR_CLI_PASSPHRASE or an interactive prompt.
+
+use anyhow::{Context, Result};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::IsTerminal;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::cache;
+use crate::error::CliError;
+
+const FILE_NAME: &str = "keys.json";
+const FORMAT_VERSION: u32 = 1;
+const PBKDF2_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+/// Sealed under a fixed label so a wrong passphrase is reported as such
+const CHECK_LABEL: &str = "__passphrase_check__";
+const CHECK_PLAINTEXT: &str = "linear-cli";
+
+/// Derived key of the store unlocked in this process, with the salt it's for
+static UNLOCKED: Mutex<Option<(String, [u8; 32])>> = Mutex::new(None);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeyFile {
+    version: u32,
+    iterations: u32,
+    /// Hex-encoded PBKDF2 salt
+    salt: String,
+    check: Sealed,
+    #[serde(default)]
+    keys: BTreeMap<String, Sealed>,
+}
+
+/// Hex-encoded nonce and ciphertext (with tag)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Sealed {
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Location of the encrypted key file
+pub fn path() -> Result<PathBuf> {
+    Ok(dirs::config_dir()
+        .context("Could not find config directory")?
+        .join("linear-cli")
+        .join(FILE_NAME))
+}
+
+/// Whether the store holds a key for `profile` (never prompts)
+pub fn has_key(profile: &str) -> bool {
+    path()
+        .ok()
+        .and_then(|p| read_file(&p).ok().flatten())
+        .is_some_and(|file| file.keys.contains_key(profile))
+}
+
+/// Decrypt the key stored for `profile`, if there is one
+pub fn get_key(profile: &str) -> Result<Option<String>> {
+    let path = path()?;
+    let Some(file) = read_file(&path)? else {
+        return Ok(None);
+    };
+    let Some(sealed) = file.keys.get(profile) else {
+        return Ok(None);
+    };
+    let key = unlock(&file, passphrase)?;
+    open(&key, profile, sealed).map(Some)
+}
+
+/// Encrypt and store keys for several profiles, creating the store if needed
+pub fn set_keys(entries: &[(String, String)]) -> Result<()> {
+    let path = path()?;
+    update(&path, passphrase, |key, file| {
+        for (profile, api_key) in entries {
+            file.keys
+                .insert(profile.clone(), seal(key, profile, api_key)?);
+        }
+        Ok(())
+    })
+}
+
+/// Encrypt and store the key for `profile`
+pub fn set_key(profile: &str, api_key: &str) -> Result<()> {
+    set_keys(&[(profile.to_string(), api_key.to_string())])
+}
+
+/// Remove the key for `profile`; removing a missing key is not an error
+pub fn delete_key(profile: &str) -> Result<()> {
+    let path = path()?;
+    let _lock = cache::lock(&path)?;
+    let Some(mut file) = read_file(&path)? else {
+        return Ok(());
+    };
+    // Deleting needs no passphrase: nothing is decrypted or sealed
+    if file.keys.remove(profile).is_some() {
+        write_file(&path, &file)?;
+    }
+    Ok(())
+}
+
+fn read_file(path: &Path) -> Result<Option<KeyFile>> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map(Some)
+            .with_context(|| format!("Invalid key store: {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_file(path: &Path, file: &KeyFile) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    cache::write_atomic(path, serde_json::to_string_pretty(file)?.as_bytes())
+}
+
+/// Read-modify-write the store under its lock, creating it on first use
+fn update(
+    path: &Path,
+    passphrase: impl FnOnce(bool) -> Result<String>,
+    modify: impl FnOnce(&[u8; 32], &mut KeyFile) -> Result<()>,
+) -> Result<()> {
+    let _lock = cache::lock(path)?;
+    let (key, mut file) = match read_file(path)? {
+        Some(file) => (unlock(&file, passphrase)?, file),
+        None => create(passphrase)?,
+    };
+    modify(&key, &mut file)?;
+    write_file(path, &file)
+}
+
+fn create(passphrase: impl FnOnce(bool) -> Result<String>) -> Result<([u8; 32], KeyFile)> {
+    let mut salt = [0u8; SALT_LEN];
+    SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_| anyhow::anyhow!("Could not generate a random salt"))?;
+    let salt = to_hex(&salt);
+    let key = derive(&passphrase(true)?, &salt, PBKDF2_ITERATIONS)?;
+    let file = KeyFile {
+        version: FORMAT_VERSION,
+        iterations: PBKDF2_ITERATIONS,
+        check: seal(&key, CHECK_LABEL, CHECK_PLAINTEXT)?,
+        salt: salt.clone(),
+        keys: BTreeMap::new(),
+    };
+    *UNLOCKED.lock().unwrap_or_else(|e| e.into_inner()) = Some((salt, key));
+    Ok((key, file))
+}
+
+/// Derive the file's key, reusing it if this process already unlocked it
+fn unlock(file: &KeyFile, passphrase: impl FnOnce(bool) -> Result<String>) -> Result<[u8; 32]> {
+    if file.version != FORMAT_VERSION {
+        anyhow::bail!("Unsupported key store version {}", file.version);
+    }
+    let mut unlocked = UNLOCKED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((salt, key)) = unlocked.as_ref() {
+        if *salt == file.salt {
+            return Ok(*key);
+        }
+    }
+
+    let key = derive(&passphrase(false)?, &file.salt, file.iterations)?;
+    match open(&key, CHECK_LABEL, &file.check) {
+        Ok(check) if check == CHECK_PLAINTEXT => {}
+        _ => return Err(CliError::new(3, "Wrong passphrase for the encrypted key store").into()),
+    }
+    *unlocked = Some((file.salt.clone(), key));
+    Ok(key)
+}
+
+/// LINEAR_CLI_PASSPHRASE, or a prompt when attached to a terminal
+fn passphrase(confirm: bool) -> Result<String> {
+    if let Ok(passphrase) = std::env::var("LINEAR_CLI_PASSPHRASE") {
+        if !passphrase.is_empty() {
+            return Ok(passphrase);
+        }
+    }
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        return Err(CliError::new(
+            3,
+            "No terminal to prompt on; set LINEAR_CLI_PASSPHRASE to unlock the encrypted key store",
+        )
+        .into());
+    }
+    let mut prompt = dialoguer::Password::new().with_prompt("Key store passphrase");
+    if confirm {
+        prompt = prompt.with_confirmation("Confirm passphrase", "Passphrases don't match");
+    }
+    Ok(prompt.interact()?)
+}
+
+fn derive(passphrase: &str, salt: &str, iterations: u32) -> Result<[u8; 32]> {
+    let iterations = NonZeroU32::new(iterations).context("Key store iterations must be > 0")?;
+    let salt = from_hex(salt)?;
+    let mut key = [0u8; 32];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    Ok(key)
+}
+
+/// Encrypt `plaintext`, binding it to `label` so entries can't be swapped
+fn seal(key: &[u8; 32], label: &str, plaintext: &str) -> Result<Sealed> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| anyhow::anyhow!("Could not generate a random nonce"))?;
+    let mut in_out = plaintext.as_bytes().to_vec();
+    cipher(key)?
+        .seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(label.as_bytes()),
+            &mut in_out,
+        )
+        .map_err(|_| anyhow::anyhow!("Could not encrypt the API key"))?;
+    Ok(Sealed {
+        nonce: to_hex(&nonce),
+        ciphertext: to_hex(&in_out),
+    })
+}
+
+fn open(key: &[u8; 32], label: &str, sealed: &Sealed) -> Result<String> {
+    let nonce = Nonce::try_assume_unique_for_key(&from_hex(&sealed.nonce)?)
+        .map_err(|_| anyhow::anyhow!("Corrupt key store entry for '{}'", label))?;
+    let mut in_out = from_hex(&sealed.ciphertext)?;
+    let plaintext = cipher(key)?
+        .open_in_place(nonce, Aad::from(label.as_bytes()), &mut in_out)
+        .map_err(|_| anyhow::anyhow!("Could not decrypt the key store entry for '{}'", label))?;
+    Ok(String::from_utf8(plaintext.to_vec())?)
+}
+
+fn cipher(key: &[u8; 32]) -> Result<LessSafeKey> {
+    let key = UnboundKey::new(&CHACHA20_POLY1305, key)
+        .map_err(|_| anyhow::anyhow!("Invalid key store key"))?;
+    Ok(LessSafeKey::new(key))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Result<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        anyhow::bail!("Invalid hex in key store");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).context("Invalid hex in key store"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passphrase_of(value: &'static str) -> impl FnOnce(bool) -> Result<String> {
+        move |_| Ok(value.to_string())
+    }
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let key = derive("hunter2", "00112233445566778899aabbccddeeff", 1000).unwrap();
+        let sealed = seal(&key, "work", "lin_api_secret").unwrap();
+        assert!(!sealed.ciphertext.contains(&to_hex(b"lin_api_secret")));
+        assert_eq!(open(&key, "work", &sealed).unwrap(), "lin_api_secret");
+        // Bound to the profile: another entry's label doesn't decrypt it
+        assert!(open(&key, "personal", &sealed).is_err());
+    }
+
+    #[test]
+    fn test_wrong_passphrase_is_rejected() {
+        let path =
+            std::env::temp_dir().join(format!("linear-cli-keys-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        update(&path, passphrase_of("right"), |key, file| {
+            file.keys
+                .insert("work".into(), seal(key, "work", "lin_api_x")?);
+            Ok(())
+        })
+        .unwrap();
+
+        let file = read_file(&path).unwrap().unwrap();
+        *UNLOCKED.lock().unwrap() = None;
+        let err = unlock(&file, passphrase_of("wrong")).unwrap_err();
+        assert_eq!(err.downcast_ref::<CliError>().map(|e| e.code), Some(3));
+        let key = unlock(&file, passphrase_of("right")).unwrap();
+        assert_eq!(open(&key, "work", &file.keys["work"]).unwrap(), "lin_api_x");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_from_hex_rejects_non_ascii() {
+        assert_eq!(from_hex("00ff").unwrap(), vec![0, 255]);
+        assert!(from_hex("é0").is_err());
+        assert!(from_hex("0g").is_err());
+        assert!(from_hex("abc").is_err());
+    }
+}
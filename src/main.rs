@@ -1,28 +1,41 @@
+mod aliases;
 mod api;
+mod batch;
 mod cache;
 mod commands;
 mod config;
 mod dates;
 mod error;
 mod input;
+mod introspection;
 mod json_path;
 #[cfg(feature = "secure-storage")]
 mod keyring;
+mod keystore;
+mod mirror;
 mod output;
 mod pagination;
 mod priority;
+mod project_config;
+mod queries;
+mod rate_limit;
+mod recording;
 mod retry;
+mod settings;
 mod text;
+mod trace;
 mod types;
 mod vcs;
 
 use anyhow::Result;
-use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use clap_complete::{generate, Shell};
 use commands::{
-    auth, bulk, comments, custom_views, cycles, doctor, documents, export, favorites, git, history,
-    initiatives, interactive, issues, labels, metrics, notifications, projects, relations,
-    roadmaps, search, statuses, sync, teams, templates, time, triage, uploads, users, watch,
+    auth, bulk, comments, custom_views, cycles, doctor, documents, export, favorites, git, graphql,
+    history, initiatives, interactive, issues, labels, mcp, metrics, notifications, outbox,
+    projects, relations, roadmaps, schema, search, statuses, sync, teams, templates, time, triage,
+    uploads, users, watch,
 };
 use error::CliError;
 use output::print_json;
@@ -67,6 +80,8 @@ pub struct AgentOptions {
     about = "A powerful CLI for Linear.app - manage issues, projects, and more from your terminal"
 )]
 #[command(version)]
+// Later occurrences win, so flags after an alias override the alias's own
+#[command(args_override_self = true)]
 #[command(after_help = r#"QUICK START:
     1. Get your API key from https://linear.app/settings/api
     2. Configure the CLI:
@@ -94,6 +109,17 @@ COMMON FLAGS:
     --schema                      Print JSON schema version and exit
     --cache-ttl N                 Cache TTL in seconds
     --no-cache                    Disable cache usage
+    --record DIR                  Record API traffic to DIR
+    --replay DIR                  Replay API traffic from DIR
+    --batch-size N                IDs per request for multi-ID get (default: 50)
+    --verbose                     Log each API call (status, latency, request id)
+    --trace                       Like --verbose, plus redacted variables
+    --trace-file FILE             Append the request log to FILE instead of stderr
+    --proxy URL                   HTTP(S) proxy for API traffic
+    --ca-cert FILE                Trust extra root certificates from a PEM bundle
+    --timeout N                   Request timeout in seconds (default: 30)
+    --connect-timeout N           Connect timeout in seconds (default: 10)
+    --offline                     Queue writes in the outbox (see: linear outbox)
 
 For more info on a command, run: linear <command> --help"#)]
 struct Cli {
@@ -209,10 +235,70 @@ struct Cli {
     #[arg(long, global = true, default_value = "0")]
     retry: u32,
 
+    /// Maximum IDs fetched per request by multi-ID get commands
+    #[arg(long, global = true, value_name = "N", env = "LINEAR_CLI_BATCH_SIZE")]
+    batch_size: Option<usize>,
+
+    /// Log each API request (operation, status, latency, request id, retries) to stderr
+    #[arg(long, global = true)]
+    verbose: bool,
+
+    /// Like --verbose, and also log request variables with secrets redacted
+    #[arg(long, global = true, env = "LINEAR_CLI_TRACE")]
+    trace: bool,
+
+    /// Append the request log to this file instead of stderr (implies --verbose)
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        env = "LINEAR_CLI_TRACE_FILE"
+    )]
+    trace_file: Option<std::path::PathBuf>,
+
+    /// HTTP(S) proxy URL for API traffic
+    #[arg(long, global = true, value_name = "URL", env = "LINEAR_CLI_PROXY")]
+    proxy: Option<String>,
+
+    /// PEM bundle of extra root certificates to trust
+    #[arg(long, global = true, value_name = "FILE", env = "LINEAR_CLI_CA_CERT")]
+    ca_cert: Option<String>,
+
+    /// Total request timeout in seconds (default: 30)
+    #[arg(long, global = true, value_name = "SECS", env = "LINEAR_CLI_TIMEOUT")]
+    timeout: Option<u64>,
+
+    /// Connect timeout in seconds (default: 10)
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECS",
+        env = "LINEAR_CLI_CONNECT_TIMEOUT"
+    )]
+    connect_timeout: Option<u64>,
+
+    /// Queue writes in the outbox instead of sending them
+    #[arg(long, global = true, env = "LINEAR_CLI_OFFLINE")]
+    offline: bool,
+
     /// Print JSON schema version info and exit
     #[arg(long, global = true)]
     schema: bool,
 
+    /// Record every GraphQL request/response pair into a directory
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        env = "LINEAR_CLI_RECORD",
+        conflicts_with = "replay"
+    )]
+    record: Option<std::path::PathBuf>,
+
+    /// Serve GraphQL responses from a recorded directory instead of the network
+    #[arg(long, global = true, value_name = "DIR", env = "LINEAR_CLI_REPLAY")]
+    replay: Option<std::path::PathBuf>,
+
     /// Show common tasks and examples
     #[command(subcommand)]
     command: Commands,
@@ -244,6 +330,40 @@ pub fn display_options() -> DisplayOptions {
     DISPLAY_OPTIONS.get().copied().unwrap_or_default()
 }
 
+/// Fill global options that weren't given on the command line or through
+/// the environment from the profile's settings and the `[defaults]` table
+/// of config.toml
+fn apply_config_defaults(
+    cli: &mut Cli,
+    matches: &ArgMatches,
+    defaults: &config::Defaults,
+) -> Result<()> {
+    let unset = |id: &str| {
+        !matches!(
+            matches.value_source(id),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        )
+    };
+    if let Some(output) = defaults.output.as_deref().filter(|_| unset("output")) {
+        cli.output = OutputFormat::from_str(output, true).map_err(|_| {
+            CliError::new(
+                5,
+                format!(
+                    "Invalid default output '{}' in config.toml (expected table, json or ndjson)",
+                    output
+                ),
+            )
+        })?;
+    }
+    if let Some(retry) = defaults.retry.filter(|_| unset("retry")) {
+        cli.retry = retry;
+    }
+    cli.page_size = cli.page_size.or(defaults.page_size);
+    cli.width = cli.width.or(defaults.width);
+    cli.cache_ttl = cli.cache_ttl.or(defaults.cache_ttl);
+    Ok(())
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Show common tasks and examples
@@ -269,6 +389,41 @@ enum Commands {
         #[arg(long)]
         check_api: bool,
     },
+    /// Send a raw GraphQL query or mutation
+    #[command(after_help = r#"EXAMPLES:
+    linear api '{ viewer { id name } }'                       # Ad-hoc query
+    linear api @query.graphql --var id=ENG-123                # Query from file
+    linear api @mutation.graphql --vars @input.json           # Variables from file
+    linear api 'query($first: Int, $after: String) {
+      issues(first: $first, after: $after) {
+        nodes { identifier title } pageInfo { hasNextPage endCursor }
+      }
+    }' --paginate issues --fields identifier,title             # Follow all pages
+
+Values passed with --var are parsed as JSON when possible (10, true, null,
+["a"]); quote them to force a string: --var 'id="123"'."#)]
+    Api {
+        /// GraphQL document, @file to read from a file, or "-" for stdin
+        query: String,
+        /// Variable as key=value (repeatable; overrides --vars)
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+        /// Variables as a JSON object, or @file.json
+        #[arg(long = "vars", value_name = "JSON")]
+        vars_file: Option<String>,
+        /// Follow pagination for the connection at this path (e.g. team.issues)
+        #[arg(long, value_name = "PATH")]
+        paginate: Option<String>,
+    },
+    /// Inspect the GraphQL schema and validate queries against it
+    #[command(after_help = r#"EXAMPLES:
+    linear schema fetch                     # Cache the schema for this profile
+    linear schema show Issue                # Show fields of a type
+    linear schema validate                  # Check embedded queries in ./src"#)]
+    Schema {
+        #[command(subcommand)]
+        action: schema::SchemaCommands,
+    },
     /// Manage projects - list, create, update, delete projects
     #[command(alias = "p")]
     #[command(after_help = r#"EXAMPLES:
@@ -417,16 +572,56 @@ enum Commands {
         #[command(subcommand)]
         action: bulk::BulkCommands,
     },
+    /// Queued writes - list, replay or drop commands saved while offline
+    #[command(alias = "ob")]
+    #[command(after_help = r#"EXAMPLES:
+    linear outbox list                      # Show queued writes
+    linear ob flush                         # Replay them in order
+    linear ob drop 1a2b3c4d                 # Discard one entry
+    linear ob drop --all                    # Discard everything
+
+issues create/update, comments create and bulk commands are queued here
+when the API is unreachable, or always with --offline."#)]
+    Outbox {
+        #[command(subcommand)]
+        action: outbox::OutboxCommands,
+    },
+    /// Local issue mirror - sync issues for fast and offline --local reads
+    #[command(after_help = r#"EXAMPLES:
+    linear mirror sync --team ENG           # Mirror all ENG issues
+    linear mirror sync                      # Fetch changes for mirrored teams
+    linear mirror sync -t ENG --comments    # Include recent comments
+    linear mirror status                    # Show mirrored teams
+    linear issues list --local -s Todo      # Query the mirror
+
+issues list, search issues and export csv/markdown read the mirror
+with --local."#)]
+    Mirror {
+        #[command(subcommand)]
+        action: commands::mirror::MirrorCommands,
+    },
     /// Manage cache - clear cached data or view status
     #[command(alias = "ca")]
     #[command(after_help = r#"EXAMPLES:
     linear cache status                     # Show cache status
     linear ca clear                         # Clear all cache
-    linear ca clear --type teams            # Clear only teams cache"#)]
+    linear ca clear --type teams            # Clear only teams cache
+    linear cache refresh                    # Prefetch every cache type
+    linear ca refresh --type teams,users    # Refresh selected types
+    linear ca refresh --background          # Refresh stale entries without waiting
+    linear cache gc                         # Prune expired entries and removed profiles"#)]
     Cache {
         #[command(subcommand)]
         action: commands::cache::CacheCommands,
     },
+    /// Run as an MCP (Model Context Protocol) server for AI agents
+    #[command(after_help = r#"EXAMPLES:
+    linear mcp serve                        # Serve tools over stdio
+    linear mcp tools                        # List exposed tools as JSON"#)]
+    Mcp {
+        #[command(subcommand)]
+        action: mcp::McpCommands,
+    },
     /// Manage notifications - view and mark as read
     #[command(alias = "n")]
     #[command(after_help = r#"EXAMPLES:
@@ -584,9 +779,18 @@ Detects issue ID from branch names like:
     linear config set api-key YOUR_API_KEY  # Set API key (alt)
     linear config get api-key               # Get API key (masked)
     linear config set profile work          # Switch profile
+    linear config set api-key-command "pass show linear/work"  # Read key from a helper
+    linear config set defaults.output json  # Default to JSON output
+    linear config unset timeout             # Back to the default timeout
+    linear config list                      # All settings and their values
+    linear config validate                  # Check config.toml for mistakes
+    linear config edit                      # Edit config.toml in $EDITOR
     linear config show                      # Show configuration
     linear config workspace-add work KEY    # Add workspace
-    linear config workspace-switch work     # Switch workspace"#)]
+    linear config workspace-switch work     # Switch workspace
+    linear config set team ENG              # Default team for this profile
+    linear config workspace-export work -f work.toml  # Share a profile (key left out)
+    linear config workspace-import work.toml          # Onboard from a shared profile"#)]
     Config {
         #[command(subcommand)]
         action: ConfigCommands,
@@ -604,19 +808,37 @@ enum ConfigCommands {
     },
     /// Get a configuration value
     Get {
-        /// Config key to retrieve (api-key, profile)
+        /// Config key to retrieve, e.g. api-key, timeout, defaults.output
+        /// (see `linear config list`)
         key: String,
         /// Output raw value without masking
         #[arg(long)]
         raw: bool,
     },
     /// Set a configuration value
+    #[command(after_help = r#"EXAMPLES:
+    linear config set timeout 60
+    linear config set defaults.branch-template "{team}/{number}-{title}"
+    linear config set aliases.mine "issues list -a me""#)]
     Set {
-        /// Config key to set (api-key, profile)
+        /// Config key to set, e.g. api-key, timeout, defaults.output, aliases.<name>
+        /// (see `linear config list`)
         key: String,
         /// Value to set
         value: String,
     },
+    /// Remove a configuration value so its default applies
+    Unset {
+        /// Config key to remove
+        key: String,
+    },
+    /// List every setting with its value, default and description
+    #[command(alias = "ls")]
+    List,
+    /// Check config.toml for unknown keys and invalid values
+    Validate,
+    /// Open config.toml in $VISUAL or $EDITOR and validate it before saving
+    Edit,
     /// Show current configuration
     Show,
     /// Generate shell completions
@@ -641,7 +863,7 @@ enum ConfigCommands {
         api_key: String,
     },
     /// List all workspaces
-    #[command(alias = "list")]
+    #[command(alias = "workspaces")]
     WorkspaceList,
     /// Switch to a different workspace
     #[command(alias = "use")]
@@ -660,11 +882,73 @@ enum ConfigCommands {
         /// Workspace name to remove
         name: String,
     },
+    /// Write a workspace's settings to a file teammates can import
+    #[command(alias = "export")]
+    #[command(after_help = r#"EXAMPLES:
+    linear config workspace-export work -f linear-work.toml
+    linear config workspace-export --include-key > backup.toml"#)]
+    WorkspaceExport {
+        /// Workspace to export (default: current)
+        name: Option<String>,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        file: Option<std::path::PathBuf>,
+        /// Include the API key (left out by default)
+        #[arg(long)]
+        include_key: bool,
+    },
+    /// Add a workspace from a file written by workspace-export
+    #[command(alias = "import")]
+    #[command(after_help = r#"EXAMPLES:
+    linear config workspace-import linear-work.toml
+    linear config workspace-import shared.toml --name staging --force"#)]
+    WorkspaceImport {
+        /// Profile file, or - for stdin
+        file: String,
+        /// Import under this name instead of the one in the file
+        #[arg(long)]
+        name: Option<String>,
+        /// Replace an existing workspace of the same name
+        #[arg(long)]
+        force: bool,
+        /// Keep the file's api_key_command and ca_cert after confirming them
+        /// (they're skipped otherwise)
+        #[arg(long)]
+        allow_command: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    // A broken config.toml surfaces from the command that reads it, not here
+    let user_config = config::load_config().unwrap_or_default();
+    let args = aliases::expand(
+        std::env::args_os().collect(),
+        &user_config.aliases,
+        &Cli::command(),
+    )?;
+    let matches = Cli::command().get_matches_from(args);
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if let Err(e) = project_config::init() {
+        // Commands that never read project defaults must still work, not
+        // least to fix the config that broke
+        if !matches!(
+            cli.command,
+            Commands::Common | Commands::Auth { .. } | Commands::Config { .. }
+        ) {
+            eprintln!("Error: {}", e);
+            std::process::exit(categorize_error(&e) as i32);
+        }
+        eprintln!("Warning: {}", e);
+    }
+    if let Some(profile) = cli.profile.as_deref() {
+        std::env::set_var("LINEAR_CLI_PROFILE", profile);
+    } else if std::env::var("LINEAR_CLI_PROFILE").is_err() {
+        if let Some(profile) = project_config::get().profile.as_deref() {
+            std::env::set_var("LINEAR_CLI_PROFILE", profile);
+        }
+    }
+    apply_config_defaults(&mut cli, &matches, &user_config.profile_defaults())?;
     if cli.no_color || cli.color == ColorChoice::Never {
         colored::control::set_override(false);
     } else if cli.color == ColorChoice::Always {
@@ -677,10 +961,34 @@ async fn main() -> Result<()> {
     if let Some(key) = cli.api_key.as_deref() {
         std::env::set_var("LINEAR_API_KEY", key);
     }
-    if let Some(profile) = cli.profile.as_deref() {
-        std::env::set_var("LINEAR_CLI_PROFILE", profile);
+    // Network flags reach the client through the same variables as their env forms
+    if let Some(proxy) = cli.proxy.as_deref() {
+        std::env::set_var("LINEAR_CLI_PROXY", proxy);
+    }
+    if let Some(ca_cert) = cli.ca_cert.as_deref() {
+        std::env::set_var("LINEAR_CLI_CA_CERT", ca_cert);
+    }
+    if let Some(timeout) = cli.timeout {
+        std::env::set_var("LINEAR_CLI_TIMEOUT", timeout.to_string());
+    }
+    if let Some(timeout) = cli.connect_timeout {
+        std::env::set_var("LINEAR_CLI_CONNECT_TIMEOUT", timeout.to_string());
     }
     api::set_default_retry(cli.retry);
+    outbox::set_offline(cli.offline);
+    if let Some(size) = cli.batch_size {
+        batch::set_chunk_size(size);
+    }
+    if cli.trace {
+        trace::init(trace::TraceLevel::Trace, cli.trace_file.as_deref())?;
+    } else if cli.verbose || cli.trace_file.is_some() {
+        trace::init(trace::TraceLevel::Verbose, cli.trace_file.as_deref())?;
+    }
+    if let Some(dir) = cli.record.clone() {
+        recording::set_mode(recording::TrafficMode::Record(dir));
+    } else if let Some(dir) = cli.replay.clone() {
+        recording::set_mode(recording::TrafficMode::Replay(dir));
+    }
     let filters = parse_filters(&cli.filter)?;
     let pagination = PaginationOptions {
         limit: cli.limit,
@@ -713,6 +1021,7 @@ async fn main() -> Result<()> {
         },
         dry_run: cli.dry_run,
     };
+    cache::set_options(output.cache);
     let agent_opts = AgentOptions {
         quiet: cli.quiet,
         id_only: cli.id_only,
@@ -732,7 +1041,12 @@ async fn main() -> Result<()> {
         std::process::exit(0);
     }
 
+    let offline = cli.offline;
     let result = run_command(cli.command, &output, agent_opts, cli.retry).await;
+    // A detached refresh would bypass the queue or the recording
+    if !offline && recording::mode().is_none() {
+        commands::cache::refresh_expired_in_background(&output);
+    }
 
     match result {
         Ok(()) => std::process::exit(0),
@@ -775,7 +1089,8 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// Categorize error for exit codes: 1=general error, 2=not found, 3=auth error
+/// Categorize error for exit codes: 1=general error, 2=not found, 3=auth error,
+/// 4=rate limited, 5=invalid input, 6=conflict
 fn categorize_error(e: &anyhow::Error) -> u8 {
     if let Some(cli_error) = e.downcast_ref::<CliError>() {
         return cli_error.code;
@@ -846,15 +1161,25 @@ async fn run_command(
         Commands::Teams { action } => teams::handle(action, output).await?,
         Commands::Users { action } => users::handle(action, output).await?,
         Commands::Cycles { action } => cycles::handle(action, output).await?,
-        Commands::Comments { action } => comments::handle(action, output).await?,
+        Commands::Comments { action } => comments::handle(action, output, agent_opts).await?,
         Commands::Documents { action } => documents::handle(action, output).await?,
         Commands::Search { action } => search::handle(action, output).await?,
         Commands::CustomViews { action } => custom_views::handle(action, output).await?,
         Commands::Sync { action } => sync::handle(action, output).await?,
         Commands::Statuses { action } => statuses::handle(action, output).await?,
         Commands::Git { action } => git::handle(action).await?,
-        Commands::Bulk { action } => bulk::handle(action, output).await?,
-        Commands::Cache { action } => commands::cache::handle(action).await?,
+        Commands::Bulk { action } => {
+            outbox::submit(
+                outbox::Operation::Bulk { command: action },
+                output,
+                agent_opts,
+            )
+            .await?
+        }
+        Commands::Outbox { action } => outbox::handle(action, output, agent_opts).await?,
+        Commands::Mirror { action } => commands::mirror::handle(action, output).await?,
+        Commands::Cache { action } => commands::cache::handle(action, output).await?,
+        Commands::Mcp { action } => mcp::handle(action, output).await?,
         Commands::Notifications { action } => notifications::handle(action, output).await?,
         Commands::Templates { action } => templates::handle(action, output).await?,
         Commands::Time { action } => time::handle(action, output).await?,
@@ -875,6 +1200,22 @@ async fn run_command(
         Commands::Relations { action } => relations::handle(action, output).await?,
         Commands::Auth { action } => auth::handle(action, output).await?,
         Commands::Doctor { check_api } => doctor::run(output, check_api).await?,
+        Commands::Schema { action } => schema::handle(action, output).await?,
+        Commands::Api {
+            query,
+            vars,
+            vars_file,
+            paginate,
+        } => {
+            graphql::run(
+                &query,
+                vars_file.as_deref(),
+                &vars,
+                paginate.as_deref(),
+                output,
+            )
+            .await?
+        }
         Commands::Config { action } => match action {
             ConfigCommands::SetKey { key } => {
                 config::set_api_key(&key)?;
@@ -888,6 +1229,20 @@ async fn run_command(
             ConfigCommands::Set { key, value } => {
                 config::config_set(&key, &value)?;
             }
+            ConfigCommands::Unset { key } => {
+                config::config_unset(&key)?;
+            }
+            ConfigCommands::List => {
+                config::config_list(output)?;
+            }
+            ConfigCommands::Validate => {
+                let cli = Cli::command();
+                config::config_validate(output, |name| aliases::is_builtin(&cli, name))?;
+            }
+            ConfigCommands::Edit => {
+                let cli = Cli::command();
+                config::config_edit(|name| aliases::is_builtin(&cli, name))?;
+            }
             ConfigCommands::Show => {
                 config::show_config()?;
             }
@@ -910,6 +1265,21 @@ async fn run_command(
             ConfigCommands::WorkspaceRemove { name } => {
                 config::workspace_remove(&name)?;
             }
+            ConfigCommands::WorkspaceExport {
+                name,
+                file,
+                include_key,
+            } => {
+                config::workspace_export(name.as_deref(), file.as_deref(), include_key)?;
+            }
+            ConfigCommands::WorkspaceImport {
+                file,
+                name,
+                force,
+                allow_command,
+            } => {
+                config::workspace_import(&file, name.as_deref(), force, allow_command)?;
+            }
         },
     }
 
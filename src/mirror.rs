@@ -0,0 +1,402 @@
+//! Local copy of issues for `--local` queries.
+//!
+//! `linear mirror sync` pulls every issue of the selected teams into
+//! `mirror.json` in the profile's cache directory. Later syncs only ask for
+//! issues whose `updatedAt` is at or past the newest one already stored, so
+//! keeping the mirror fresh costs a page or two. `issues list`,
+//! `search issues` and `export` read it with `--local`: instant, and usable
+//! without a network connection.
+
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::api::LinearClient;
+use crate::cache;
+use crate::error::CliError;
+use crate::pagination::{paginate_nodes, PaginationOptions};
+
+const MIRROR_FILE: &str = "mirror.json";
+
+/// Everything `issues list`, `search issues` and `export` show
+const ISSUE_FIELDS: &str = r#"
+    id
+    identifier
+    title
+    description
+    priority
+    estimate
+    dueDate
+    url
+    createdAt
+    updatedAt
+    archivedAt
+    trashed
+    state { id name type }
+    assignee { id name email }
+    team { id key name }
+    labels { nodes { id name color } }
+    project { id name }
+    cycle { id number name }
+"#;
+
+const COMMENT_FIELDS: &str = r#"
+    comments(first: 50) {
+        nodes { id body createdAt user { id name } }
+    }
+"#;
+
+/// Sync progress for one team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamSync {
+    pub key: String,
+    pub name: String,
+    /// Newest `updatedAt` seen; the next sync asks for issues from here on
+    pub updated_after: Option<String>,
+    pub synced_at: String,
+    #[serde(default)]
+    pub comments: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Mirror {
+    /// Mirrored teams by team ID
+    #[serde(default)]
+    pub teams: BTreeMap<String, TeamSync>,
+    /// Raw issue JSON by issue ID
+    #[serde(default)]
+    pub issues: BTreeMap<String, Value>,
+}
+
+fn mirror_path() -> Result<PathBuf> {
+    Ok(cache::cache_dir_path()?.join(MIRROR_FILE))
+}
+
+impl Mirror {
+    /// The stored mirror, or an empty one if nothing has been synced
+    pub fn load() -> Result<Self> {
+        let path = mirror_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map_err(|e| {
+            anyhow::anyhow!(
+                "Local mirror {} is corrupt ({}); run `linear mirror clear` and sync again",
+                path.display(),
+                e
+            )
+        })
+    }
+
+    /// The stored mirror for `--local` reads; errors if nothing was synced
+    pub fn load_synced() -> Result<Self> {
+        let mirror = Self::load()?;
+        if mirror.teams.is_empty() {
+            return Err(CliError::new(
+                2,
+                "No local mirror for this profile; run `linear mirror sync --team KEY` first",
+            )
+            .into());
+        }
+        Ok(mirror)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        cache::write_atomic(&mirror_path()?, serde_json::to_string(self)?.as_bytes())
+    }
+
+    pub fn clear() -> Result<()> {
+        let path = mirror_path()?;
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Mirrored issues, most recently updated first
+    pub fn issues(&self, include_archived: bool) -> Vec<Value> {
+        let mut issues: Vec<Value> = self
+            .issues
+            .values()
+            .filter(|issue| include_archived || issue["archivedAt"].is_null())
+            .cloned()
+            .collect();
+        issues.sort_by(|a, b| {
+            let key = |v: &Value| v["updatedAt"].as_str().unwrap_or("").to_string();
+            key(b).cmp(&key(a))
+        });
+        issues
+    }
+
+    /// Store fetched issues, dropping deleted ones; returns how many changed
+    fn upsert(&mut self, nodes: Vec<Value>) -> usize {
+        let count = nodes.len();
+        for node in nodes {
+            let Some(id) = node["id"].as_str().map(|s| s.to_string()) else {
+                continue;
+            };
+            if node["trashed"].as_bool() == Some(true) {
+                self.issues.remove(&id);
+            } else {
+                self.issues.insert(id, node);
+            }
+        }
+        count
+    }
+
+    pub fn count_for_team(&self, team_id: &str) -> usize {
+        self.issues
+            .values()
+            .filter(|issue| issue["team"]["id"].as_str() == Some(team_id))
+            .count()
+    }
+}
+
+/// Pull issues of one team updated since its last sync (or all with `full`).
+/// Returns the number of issues fetched.
+pub async fn sync_team(
+    client: &LinearClient,
+    mirror: &mut Mirror,
+    team: &Value,
+    comments: bool,
+    full: bool,
+) -> Result<usize> {
+    let team_id = team["id"].as_str().unwrap_or_default().to_string();
+    let previous = mirror.teams.get(&team_id).cloned();
+    // A sync that adds comments has to revisit issues that haven't changed
+    let since = previous
+        .as_ref()
+        .filter(|p| !full && (p.comments || !comments))
+        .and_then(|p| p.updated_after.clone());
+
+    let query = format!(
+        r#"
+        query($filter: IssueFilter, $first: Int, $after: String, $last: Int, $before: String) {{
+            issues(first: $first, after: $after, last: $last, before: $before, includeArchived: true, orderBy: updatedAt, filter: $filter) {{
+                nodes {{ {}{} }}
+                pageInfo {{ hasNextPage endCursor hasPreviousPage startCursor }}
+            }}
+        }}
+    "#,
+        ISSUE_FIELDS,
+        if comments { COMMENT_FIELDS } else { "" }
+    );
+
+    let mut filter = json!({ "team": { "id": { "eq": team_id } } });
+    if let Some(ref since) = since {
+        // gte: issues updated in the same millisecond as the last one seen
+        // must not be skipped; re-storing one twice is harmless
+        filter["updatedAt"] = json!({ "gte": since });
+    }
+    let mut variables = Map::new();
+    variables.insert("filter".to_string(), filter);
+
+    let pagination = PaginationOptions {
+        all: true,
+        page_size: Some(if comments { 50 } else { 100 }),
+        ..Default::default()
+    };
+    let nodes = paginate_nodes(
+        client,
+        &query,
+        variables,
+        &["data", "issues", "nodes"],
+        &["data", "issues", "pageInfo"],
+        &pagination,
+        100,
+    )
+    .await?;
+
+    let newest = nodes
+        .iter()
+        .filter_map(|n| n["updatedAt"].as_str())
+        .max()
+        .map(|s| s.to_string());
+    let updated_after = match (newest, since) {
+        (Some(newest), Some(since)) => Some(newest.max(since)),
+        (newest, since) => newest.or(since),
+    };
+
+    if full {
+        mirror
+            .issues
+            .retain(|_, issue| issue["team"]["id"].as_str() != Some(team_id.as_str()));
+    }
+    let fetched = mirror.upsert(nodes);
+    mirror.teams.insert(
+        team_id,
+        TeamSync {
+            key: team["key"].as_str().unwrap_or_default().to_string(),
+            name: team["name"].as_str().unwrap_or_default().to_string(),
+            updated_after,
+            synced_at: Utc::now().to_rfc3339(),
+            comments: comments || previous.is_some_and(|p| p.comments),
+        },
+    );
+    Ok(fetched)
+}
+
+/// Case-insensitive match of `name` against a nested object's ID, `name` or `key`
+pub fn names_match(value: &Value, name: &str) -> bool {
+    ["id", "name", "key"].iter().any(|field| {
+        value[field]
+            .as_str()
+            .is_some_and(|v| v.eq_ignore_ascii_case(name))
+    })
+}
+
+/// `issues list` filters applied to mirrored issues
+#[derive(Debug, Default)]
+pub struct IssueFilter {
+    pub team: Option<String>,
+    pub state: Option<String>,
+    pub assignee: Option<String>,
+    pub project: Option<String>,
+    pub label: Option<String>,
+    pub cycle: Option<String>,
+    /// Date comparator as sent to the API, e.g. `{"lt": "2026-01-01"}`
+    pub due: Option<Value>,
+}
+
+impl IssueFilter {
+    pub fn matches(&self, issue: &Value) -> bool {
+        let nested = |field: &str, wanted: &Option<String>| {
+            wanted
+                .as_deref()
+                .is_none_or(|name| names_match(&issue[field], name))
+        };
+        nested("team", &self.team)
+            && nested("state", &self.state)
+            && nested("project", &self.project)
+            && self.assignee.as_deref().is_none_or(|name| {
+                names_match(&issue["assignee"], name)
+                    || issue["assignee"]["email"]
+                        .as_str()
+                        .is_some_and(|e| e.eq_ignore_ascii_case(name))
+            })
+            && self.label.as_deref().is_none_or(|name| {
+                issue["labels"]["nodes"]
+                    .as_array()
+                    .is_some_and(|labels| labels.iter().any(|l| names_match(l, name)))
+            })
+            && self.cycle.as_deref().is_none_or(|cycle| {
+                names_match(&issue["cycle"], cycle)
+                    || issue["cycle"]["number"]
+                        .as_u64()
+                        .is_some_and(|n| n.to_string() == cycle)
+            })
+            && self
+                .due
+                .as_ref()
+                .is_none_or(|cmp| date_matches(cmp, issue["dueDate"].as_str()))
+    }
+}
+
+/// Evaluate an API date comparator (`eq`, `lt`, `gte`, `lte`, `null`)
+/// against a `YYYY-MM-DD` date
+fn date_matches(comparator: &Value, date: Option<&str>) -> bool {
+    let Some(ops) = comparator.as_object() else {
+        return true;
+    };
+    ops.iter().all(|(op, bound)| {
+        if op == "null" {
+            return date.is_none() == bound.as_bool().unwrap_or(false);
+        }
+        let (Some(date), Some(bound)) = (date, bound.as_str()) else {
+            return false;
+        };
+        match op.as_str() {
+            "eq" => date == bound,
+            "lt" => date < bound,
+            "lte" => date <= bound,
+            "gt" => date > bound,
+            "gte" => date >= bound,
+            _ => true,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upsert_replaces_and_drops_trashed() {
+        let mut mirror = Mirror::default();
+        mirror.upsert(vec![
+            json!({ "id": "a", "title": "Old", "updatedAt": "2026-01-01T00:00:00.000Z" }),
+            json!({ "id": "b", "title": "Gone", "updatedAt": "2026-01-02T00:00:00.000Z" }),
+        ]);
+        mirror.upsert(vec![
+            json!({ "id": "a", "title": "New", "updatedAt": "2026-01-03T00:00:00.000Z" }),
+            json!({ "id": "b", "trashed": true }),
+        ]);
+        let issues = mirror.issues(false);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0]["title"], "New");
+    }
+
+    #[test]
+    fn test_issues_sorted_newest_first_without_archived() {
+        let mut mirror = Mirror::default();
+        mirror.upsert(vec![
+            json!({ "id": "a", "updatedAt": "2026-01-01T00:00:00.000Z" }),
+            json!({ "id": "b", "updatedAt": "2026-01-03T00:00:00.000Z" }),
+            json!({ "id": "c", "updatedAt": "2026-01-02T00:00:00.000Z", "archivedAt": "2026-01-02T00:00:00.000Z" }),
+        ]);
+        let ids: Vec<String> = mirror
+            .issues(false)
+            .iter()
+            .map(|i| i["id"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, vec!["b", "a"]);
+        assert_eq!(mirror.issues(true).len(), 3);
+    }
+
+    #[test]
+    fn test_issue_filter() {
+        let issue = json!({
+            "team": { "id": "t1", "key": "ENG", "name": "Engineering" },
+            "state": { "name": "In Progress" },
+            "assignee": { "name": "Ada", "email": "ada@example.com" },
+            "labels": { "nodes": [{ "id": "l1", "name": "bug" }] },
+            "cycle": { "id": "c1", "number": 7, "name": null },
+            "dueDate": "2026-03-10",
+        });
+        assert!(IssueFilter::default().matches(&issue));
+        let filter = IssueFilter {
+            team: Some("eng".to_string()),
+            state: Some("in progress".to_string()),
+            assignee: Some("ada@example.com".to_string()),
+            label: Some("Bug".to_string()),
+            cycle: Some("7".to_string()),
+            due: Some(json!({ "gte": "2026-03-09", "lte": "2026-03-15" })),
+            ..Default::default()
+        };
+        assert!(filter.matches(&issue));
+        let filter = IssueFilter {
+            project: Some("Roadmap".to_string()),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&issue));
+        let filter = IssueFilter {
+            due: Some(json!({ "null": true })),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&issue));
+    }
+
+    #[test]
+    fn test_names_match_key_or_name() {
+        let team = json!({ "key": "ENG", "name": "Engineering" });
+        assert!(names_match(&team, "eng"));
+        assert!(names_match(&team, "engineering"));
+        assert!(!names_match(&team, "design"));
+        assert!(!names_match(&Value::Null, "eng"));
+    }
+}
@@ -1,8 +1,9 @@
 use anyhow::Result;
 use clap::ValueEnum;
 use serde_json::{Map, Value};
+use std::borrow::Borrow;
 use std::cmp::Ordering;
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock};
 
 use regex::Regex;
 
@@ -130,6 +131,23 @@ fn parse_filter(input: &str) -> Result<FilterExpr> {
     })
 }
 
+/// Buffer that receives `print_json` output instead of stdout while set
+static CAPTURE: Mutex<Option<Vec<Value>>> = Mutex::new(None);
+
+/// Start capturing `print_json` output (used when stdout carries a protocol)
+pub fn begin_capture() {
+    *CAPTURE.lock().unwrap_or_else(|e| e.into_inner()) = Some(Vec::new());
+}
+
+/// Stop capturing and return every value printed since `begin_capture`
+pub fn end_capture() -> Vec<Value> {
+    CAPTURE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .take()
+        .unwrap_or_default()
+}
+
 pub fn print_json(value: &Value, output: &OutputOptions) -> Result<()> {
     let mut out = value.clone();
     apply_filters(&mut out, &output.filters);
@@ -146,6 +164,11 @@ pub fn print_json(value: &Value, output: &OutputOptions) -> Result<()> {
         }
     }
 
+    if let Some(captured) = CAPTURE.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        captured.push(out);
+        return Ok(());
+    }
+
     if let Some(template) = output.format_template.as_deref() {
         return print_template(&out, template);
     }
@@ -211,11 +234,12 @@ fn apply_filters(value: &mut Value, filters: &[FilterExpr]) {
     *items = filtered;
 }
 
-pub fn filter_values(values: &mut Vec<Value>, filters: &[FilterExpr]) {
+/// Filter items by their JSON form (plain values or decoded `queries::Fetched` rows)
+pub fn filter_values<T: Borrow<Value>>(values: &mut Vec<T>, filters: &[FilterExpr]) {
     if filters.is_empty() {
         return;
     }
-    values.retain(|value| matches_filters(value, filters));
+    values.retain(|value| matches_filters(value.borrow(), filters));
 }
 
 fn matches_filters(value: &Value, filters: &[FilterExpr]) -> bool {
@@ -266,7 +290,7 @@ pub fn print_template(value: &Value, template: &str) -> Result<()> {
     Ok(())
 }
 
-pub fn ensure_non_empty(values: &[Value], output: &OutputOptions) -> Result<()> {
+pub fn ensure_non_empty<T>(values: &[T], output: &OutputOptions) -> Result<()> {
     if output.fail_on_empty && values.is_empty() {
         return Err(CliError::new(2, "No results found").into());
     }
@@ -406,10 +430,10 @@ fn extract_sort_key(value: &Value, key: &str) -> SortKey {
     }
 }
 
-pub fn sort_values(values: &mut Vec<Value>, key: &str, order: SortOrder) {
-    let mut indexed: Vec<(usize, Value)> = values.drain(..).enumerate().collect();
+pub fn sort_values<T: Borrow<Value>>(values: &mut Vec<T>, key: &str, order: SortOrder) {
+    let mut indexed: Vec<(usize, T)> = values.drain(..).enumerate().collect();
     indexed.sort_by(|(idx_a, a), (idx_b, b)| {
-        let ord = compare_json_field(a, b, key);
+        let ord = compare_json_field(a.borrow(), b.borrow(), key);
         let ord = match order {
             SortOrder::Asc => ord,
             SortOrder::Desc => ord.reverse(),
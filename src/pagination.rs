@@ -4,6 +4,7 @@ use std::future::Future;
 
 use crate::api::LinearClient;
 use crate::json_path::get_path;
+use crate::rate_limit;
 
 #[derive(Debug, Clone, Default)]
 pub struct PaginationOptions {
@@ -137,6 +138,9 @@ pub async fn paginate_nodes(
                 break;
             }
         }
+        if options.all {
+            rate_limit::throttle().await;
+        }
     }
 
     Ok(items)
@@ -284,6 +288,9 @@ where
         if after.is_none() {
             break;
         }
+        if options.all {
+            rate_limit::throttle().await;
+        }
     }
 
     Ok(total)
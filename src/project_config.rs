@@ -0,0 +1,148 @@
+//! Per-directory defaults from a `.linear.toml`, found by walking up from the
+//! current directory the way git finds `.git`. Values only fill in options
+//! that weren't given on the command line.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::error::CliError;
+use crate::settings;
+
+pub const FILE_NAME: &str = ".linear.toml";
+
+static PROJECT_CONFIG: OnceLock<ProjectConfig> = OnceLock::new();
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Team key, name or ID for creating and listing issues
+    pub team: Option<String>,
+    /// Project name or ID for creating and listing issues
+    pub project: Option<String>,
+    /// Labels added to new issues
+    pub labels: Vec<String>,
+    /// Cycle name, number or ID for new issues
+    pub cycle: Option<String>,
+    /// Branch name for `git checkout`, e.g. "{team}/{number}-{title}"
+    pub branch_template: Option<String>,
+    /// Profile used unless --profile or LINEAR_CLI_PROFILE is set
+    pub profile: Option<String>,
+    /// File the values were read from
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// Nearest `.linear.toml` in `start` or one of its ancestors
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(FILE_NAME))
+        .find(|path| path.is_file())
+}
+
+/// Parse a `.linear.toml`
+pub fn load(path: &Path) -> Result<ProjectConfig> {
+    let content = fs::read_to_string(path)?;
+    let mut config: ProjectConfig = toml::from_str(&content).map_err(|e| {
+        CliError::new(
+            5,
+            format!("Invalid {}: {}", path.display(), e.message().trim()),
+        )
+    })?;
+    if let Some(template) = &config.branch_template {
+        // Same rules as the defaults.branch-template setting
+        let key = settings::find("defaults.branch-template")?;
+        settings::parse(&key, template).map_err(|e| {
+            CliError::new(
+                5,
+                format!(
+                    "Invalid {}: {}",
+                    path.display(),
+                    e.to_string().replacen(&key.name(), "branch_template", 1)
+                ),
+            )
+        })?;
+    }
+    config.path = Some(path.to_path_buf());
+    Ok(config)
+}
+
+/// Find and load the project config for this invocation. Setting
+/// LINEAR_CLI_NO_PROJECT_CONFIG ignores any `.linear.toml`. On error no
+/// project config applies, and the message says how to skip the file.
+pub fn init() -> Result<()> {
+    let ignored = std::env::var("LINEAR_CLI_NO_PROJECT_CONFIG").is_ok_and(|v| !v.is_empty());
+    let config = match std::env::current_dir().ok().and_then(|dir| discover(&dir)) {
+        Some(path) if !ignored => load(&path).map_err(|e| {
+            let code = e.downcast_ref::<CliError>().map_or(1, |e| e.code);
+            CliError::new(
+                code,
+                format!(
+                    "{} (fix or remove the file, or set LINEAR_CLI_NO_PROJECT_CONFIG=1 to ignore it)",
+                    e
+                ),
+            )
+        })?,
+        _ => ProjectConfig::default(),
+    };
+    let _ = PROJECT_CONFIG.set(config);
+    Ok(())
+}
+
+/// Project config of this invocation (empty if there is none)
+pub fn get() -> &'static ProjectConfig {
+    PROJECT_CONFIG.get_or_init(ProjectConfig::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discover_walks_up_to_nearest_file() {
+        let root = std::env::temp_dir().join(format!("linear-cli-project-{}", std::process::id()));
+        let nested = root.join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join(FILE_NAME), "team = \"ENG\"\nlabels = [\"cli\"]\n").unwrap();
+
+        let path = discover(&nested).unwrap();
+        assert_eq!(path, root.join(FILE_NAME));
+        let config = load(&path).unwrap();
+        assert_eq!(config.team.as_deref(), Some("ENG"));
+        assert_eq!(config.labels, vec!["cli"]);
+
+        fs::write(root.join("a").join(FILE_NAME), "project = \"CLI\"\n").unwrap();
+        assert_eq!(discover(&nested).unwrap(), root.join("a").join(FILE_NAME));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        let path = std::env::temp_dir().join(format!("linear-cli-bad-{}.toml", std::process::id()));
+        fs::write(&path, "teem = \"ENG\"\n").unwrap();
+        assert!(load(&path).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_branch_template_is_validated() {
+        let path =
+            std::env::temp_dir().join(format!("linear-cli-branch-{}.toml", std::process::id()));
+        fs::write(&path, "branch_template = \"{team}/{number}-{title}\"\n").unwrap();
+        assert!(load(&path).is_ok());
+
+        fs::write(&path, "branch_template = \"{team}/{nubmer}\"\n").unwrap();
+        let err = load(&path).unwrap_err();
+        let cli = err.downcast_ref::<CliError>().unwrap();
+        assert_eq!(cli.code, 5);
+        assert!(
+            cli.message.contains("branch_template must be"),
+            "{}",
+            cli.message
+        );
+        let _ = fs::remove_file(&path);
+    }
+}
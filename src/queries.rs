@@ -0,0 +1,392 @@
+//! Typed query functions for issues, projects, cycles and comments.
+//!
+//! Each function runs a GraphQL query and decodes the result into the structs
+//! from `types.rs`, so a field the API stopped returning fails loudly instead
+//! of rendering as a blank cell. The raw JSON is kept alongside the decoded
+//! value so `--output json` keeps printing exactly what the API returned.
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use std::borrow::Borrow;
+
+use crate::api::LinearClient;
+use crate::batch::{self, BatchQuery};
+use crate::error::CliError;
+use crate::pagination::{paginate_nodes, PaginationOptions};
+use crate::types::{Comment, Cycle, Issue, IssueRef, Project, Team};
+
+/// A decoded API object together with the JSON it was decoded from
+#[derive(Debug, Clone)]
+pub struct Fetched<T> {
+    pub raw: Value,
+    pub data: T,
+}
+
+impl<T> Borrow<Value> for Fetched<T> {
+    fn borrow(&self) -> &Value {
+        &self.raw
+    }
+}
+
+impl<T: DeserializeOwned> Fetched<T> {
+    /// Decode `raw` as `T`; `what` names the object in error messages
+    pub fn decode(raw: Value, what: &str) -> Result<Self> {
+        let data = serde_json::from_value(raw.clone()).map_err(|e| {
+            CliError::new(1, format!("Unexpected {} in API response: {}", what, e)).with_details(
+                json!({
+                    "type": what,
+                    "error": e.to_string(),
+                }),
+            )
+        })?;
+        Ok(Self { raw, data })
+    }
+}
+
+/// Raw JSON of each item, for `--output json`
+pub fn raw_values<T>(items: &[Fetched<T>]) -> Value {
+    Value::Array(items.iter().map(|item| item.raw.clone()).collect())
+}
+
+/// Whether an error is the not-found error from a `get_*` function
+pub fn is_not_found(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<CliError>()
+        .map(|e| e.code == 2)
+        .unwrap_or(false)
+}
+
+fn decode_all<T: DeserializeOwned>(nodes: Vec<Value>, what: &str) -> Result<Vec<Fetched<T>>> {
+    nodes
+        .into_iter()
+        .map(|node| Fetched::decode(node, what))
+        .collect()
+}
+
+/// Decode a single object, mapping `null` to a not-found error
+fn decode_one<T: DeserializeOwned>(value: &Value, what: &str, id: &str) -> Result<Fetched<T>> {
+    if value.is_null() {
+        let mut label = what.to_string();
+        label[..1].make_ascii_uppercase();
+        return Err(CliError::new(2, format!("{} not found: {}", label, id)).into());
+    }
+    Fetched::decode(value.clone(), what)
+}
+
+async fn fetch_decoded<T: DeserializeOwned>(
+    client: &LinearClient,
+    spec: &BatchQuery<'_>,
+    ids: &[String],
+    what: &str,
+) -> Result<Vec<(String, Result<Fetched<T>>)>> {
+    Ok(batch::fetch_by_ids(client, spec, ids)
+        .await?
+        .into_iter()
+        .map(|(id, result)| {
+            let decoded = result.and_then(|raw| Fetched::decode(raw, what));
+            (id, decoded)
+        })
+        .collect())
+}
+
+/// Unwrap the only result of a one-ID batch
+fn single<T>(results: Vec<(String, Result<T>)>) -> Result<T> {
+    results
+        .into_iter()
+        .next()
+        .map(|(_, result)| result)
+        .unwrap_or_else(|| Err(CliError::new(1, "Empty batch response").into()))
+}
+
+const ISSUE_LIST_QUERY: &str = r#"
+    query($filter: IssueFilter, $includeArchived: Boolean, $first: Int, $after: String, $last: Int, $before: String) {
+        issues(
+            first: $first,
+            after: $after,
+            last: $last,
+            before: $before,
+            includeArchived: $includeArchived,
+            filter: $filter
+        ) {
+            nodes {
+                id
+                identifier
+                title
+                priority
+                dueDate
+                state { id name }
+                assignee { id name }
+            }
+            pageInfo { hasNextPage endCursor hasPreviousPage startCursor }
+        }
+    }
+"#;
+
+const ISSUE_DETAIL: &str = r#"
+    fragment IssueDetail on Issue {
+        id
+        identifier
+        title
+        description
+        priority
+        dueDate
+        url
+        createdAt
+        updatedAt
+        attachments { nodes { id title url createdAt } }
+        documents { nodes { id title url updatedAt } }
+        state { id name }
+        team { id key name }
+        assignee { id name email }
+        labels { nodes { id name color } }
+        project { id name }
+        parent { id identifier title }
+    }
+"#;
+
+const ISSUE_REF_QUERY: &str = r#"
+    query($id: String!) {
+        issue(id: $id) {
+            id
+            identifier
+            title
+        }
+    }
+"#;
+
+const PROJECT_LIST_QUERY: &str = r#"
+    query($includeArchived: Boolean, $first: Int, $after: String, $last: Int, $before: String) {
+        projects(first: $first, after: $after, last: $last, before: $before, includeArchived: $includeArchived) {
+            nodes {
+                id
+                name
+                state
+                url
+                startDate
+                targetDate
+            }
+            pageInfo { hasNextPage endCursor hasPreviousPage startCursor }
+        }
+    }
+"#;
+
+const PROJECT_DETAIL: &str = r#"
+    fragment ProjectDetail on Project {
+        id
+        name
+        description
+        icon
+        color
+        url
+        status { id name }
+        labels { nodes { id name color parent { id name } } }
+    }
+"#;
+
+const TEAM_QUERY: &str = r#"
+    query($id: String!) {
+        team(id: $id) {
+            id
+            key
+            name
+        }
+    }
+"#;
+
+const CYCLE_LIST_QUERY: &str = r#"
+    query($teamId: String!, $first: Int, $after: String, $last: Int, $before: String) {
+        team(id: $teamId) {
+            cycles(first: $first, after: $after, last: $last, before: $before) {
+                nodes {
+                    id
+                    name
+                    number
+                    startsAt
+                    endsAt
+                    completedAt
+                    progress
+                }
+                pageInfo { hasNextPage endCursor hasPreviousPage startCursor }
+            }
+        }
+    }
+"#;
+
+const COMMENT_LIST_QUERY: &str = r#"
+    query($issueId: String!, $first: Int, $after: String, $last: Int, $before: String) {
+        issue(id: $issueId) {
+            comments(first: $first, after: $after, last: $last, before: $before) {
+                nodes {
+                    id
+                    body
+                    createdAt
+                    user { id name email }
+                    parent { id }
+                }
+                pageInfo { hasNextPage endCursor hasPreviousPage startCursor }
+            }
+        }
+    }
+"#;
+
+/// List issues matching the `filter`/`includeArchived` variables
+pub async fn list_issues(
+    client: &LinearClient,
+    variables: Map<String, Value>,
+    pagination: &PaginationOptions,
+) -> Result<Vec<Fetched<Issue>>> {
+    let nodes = paginate_nodes(
+        client,
+        ISSUE_LIST_QUERY,
+        variables,
+        &["data", "issues", "nodes"],
+        &["data", "issues", "pageInfo"],
+        pagination,
+        50,
+    )
+    .await?;
+    decode_all(nodes, "issue")
+}
+
+/// Fetch issues with the details shown by `issues get`, batched by `batch`
+pub async fn get_issues(
+    client: &LinearClient,
+    ids: &[String],
+) -> Result<Vec<(String, Result<Fetched<Issue>>)>> {
+    let spec = BatchQuery {
+        field: "issue",
+        fragment_name: "IssueDetail",
+        fragment: ISSUE_DETAIL,
+        what: "Issue",
+    };
+    fetch_decoded(client, &spec, ids, "issue").await
+}
+
+pub async fn get_issue(client: &LinearClient, id: &str) -> Result<Fetched<Issue>> {
+    single(get_issues(client, &[id.to_string()]).await?)
+}
+
+/// Fetch just the identifier and title of an issue
+pub async fn get_issue_ref(client: &LinearClient, id: &str) -> Result<Fetched<IssueRef>> {
+    let result = client
+        .query(ISSUE_REF_QUERY, Some(json!({ "id": id })))
+        .await?;
+    decode_one(&result["data"]["issue"], "issue", id)
+}
+
+pub async fn list_projects(
+    client: &LinearClient,
+    include_archived: bool,
+    pagination: &PaginationOptions,
+) -> Result<Vec<Fetched<Project>>> {
+    let mut variables = Map::new();
+    variables.insert("includeArchived".to_string(), json!(include_archived));
+    let nodes = paginate_nodes(
+        client,
+        PROJECT_LIST_QUERY,
+        variables,
+        &["data", "projects", "nodes"],
+        &["data", "projects", "pageInfo"],
+        pagination,
+        50,
+    )
+    .await?;
+    decode_all(nodes, "project")
+}
+
+pub async fn get_projects(
+    client: &LinearClient,
+    ids: &[String],
+) -> Result<Vec<(String, Result<Fetched<Project>>)>> {
+    let spec = BatchQuery {
+        field: "project",
+        fragment_name: "ProjectDetail",
+        fragment: PROJECT_DETAIL,
+        what: "Project",
+    };
+    fetch_decoded(client, &spec, ids, "project").await
+}
+
+pub async fn get_project(client: &LinearClient, id: &str) -> Result<Fetched<Project>> {
+    single(get_projects(client, &[id.to_string()]).await?)
+}
+
+pub async fn get_team(client: &LinearClient, id: &str) -> Result<Fetched<Team>> {
+    let result = client.query(TEAM_QUERY, Some(json!({ "id": id }))).await?;
+    decode_one(&result["data"]["team"], "team", id)
+}
+
+pub async fn list_cycles(
+    client: &LinearClient,
+    team_id: &str,
+    pagination: &PaginationOptions,
+) -> Result<Vec<Fetched<Cycle>>> {
+    let mut variables = Map::new();
+    variables.insert("teamId".to_string(), json!(team_id));
+    let nodes = paginate_nodes(
+        client,
+        CYCLE_LIST_QUERY,
+        variables,
+        &["data", "team", "cycles", "nodes"],
+        &["data", "team", "cycles", "pageInfo"],
+        pagination,
+        50,
+    )
+    .await?;
+    decode_all(nodes, "cycle")
+}
+
+pub async fn list_comments(
+    client: &LinearClient,
+    issue_id: &str,
+    pagination: &PaginationOptions,
+) -> Result<Vec<Fetched<Comment>>> {
+    let mut variables = Map::new();
+    variables.insert("issueId".to_string(), json!(issue_id));
+    let nodes = paginate_nodes(
+        client,
+        COMMENT_LIST_QUERY,
+        variables,
+        &["data", "issue", "comments", "nodes"],
+        &["data", "issue", "comments", "pageInfo"],
+        pagination,
+        100,
+    )
+    .await?;
+    decode_all(nodes, "comment")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_keeps_raw_json() {
+        let raw = json!({ "id": "i1", "identifier": "ENG-1", "title": "T", "extra": 1 });
+        let issue: Fetched<Issue> = Fetched::decode(raw.clone(), "issue").unwrap();
+        assert_eq!(issue.data.identifier, "ENG-1");
+        assert_eq!(issue.raw, raw);
+    }
+
+    #[test]
+    fn test_decode_missing_field_is_error() {
+        let raw =
+            json!({ "id": "i1", "identifier": "ENG-1", "title": "T", "state": { "name": "Done" } });
+        let err = Fetched::<Issue>::decode(raw, "issue").unwrap_err();
+        let cli = err.downcast_ref::<CliError>().unwrap();
+        assert_eq!(cli.code, 1);
+        assert!(
+            cli.message.contains("missing field `id`"),
+            "{}",
+            cli.message
+        );
+    }
+
+    #[test]
+    fn test_decode_one_null_is_not_found() {
+        let err = decode_one::<Project>(&Value::Null, "project", "P-1").unwrap_err();
+        let cli = err.downcast_ref::<CliError>().unwrap();
+        assert_eq!(cli.code, 2);
+        assert_eq!(cli.message, "Project not found: P-1");
+    }
+}
@@ -0,0 +1,313 @@
+//! Client-side view of Linear's rate-limit budget.
+//!
+//! Every response carries `x-ratelimit-*` headers with the remaining request
+//! and complexity allowance for the current window. The latest values are kept
+//! in memory and written to the profile's cache directory whenever they move
+//! noticeably, so a script running many short invocations still sees the
+//! budget the previous one left behind without a file write per request.
+//! Bulk commands and `--all` pagination call `throttle` between requests to
+//! slow down before the API starts answering with 429s.
+
+use anyhow::Result;
+use colored::Colorize;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::cache;
+
+const BUDGET_FILE: &str = "rate_limit.json";
+
+/// Start pacing requests once less than this fraction of a budget is left
+const LOW_BUDGET_RATIO: f64 = 0.1;
+
+/// Longest single pause; the window resets hourly, so never wait it out whole
+const MAX_WAIT: Duration = Duration::from_secs(30);
+
+/// Rewrite the budget file at least this often while requests keep coming
+const SAVE_INTERVAL_MS: u64 = 10_000;
+
+/// ...and sooner once a budget moved by this fraction of its limit
+const SAVE_DELTA_RATIO: f64 = 0.05;
+
+/// Remaining allowance as reported by the last API response
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitBudget {
+    pub requests_limit: Option<u64>,
+    pub requests_remaining: Option<u64>,
+    /// Unix time in milliseconds when the request budget refills
+    pub requests_reset: Option<u64>,
+    pub complexity_limit: Option<u64>,
+    pub complexity_remaining: Option<u64>,
+    /// Unix time in milliseconds when the complexity budget refills
+    pub complexity_reset: Option<u64>,
+    /// Complexity of the request that reported this budget
+    pub last_complexity: Option<u64>,
+    /// Unix time in milliseconds when this budget was observed
+    pub observed_at: u64,
+}
+
+impl RateLimitBudget {
+    /// Parse the rate-limit headers of a response, if it has any
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let get = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+        let budget = Self {
+            requests_limit: get("x-ratelimit-requests-limit"),
+            requests_remaining: get("x-ratelimit-requests-remaining"),
+            requests_reset: get("x-ratelimit-requests-reset"),
+            complexity_limit: get("x-ratelimit-complexity-limit"),
+            complexity_remaining: get("x-ratelimit-complexity-remaining"),
+            complexity_reset: get("x-ratelimit-complexity-reset"),
+            last_complexity: get("x-complexity"),
+            observed_at: now_ms(),
+        };
+        if budget.requests_remaining.is_none() && budget.complexity_remaining.is_none() {
+            return None;
+        }
+        Some(budget)
+    }
+
+    /// Whether this differs enough from the budget last written to disk to
+    /// be worth writing: a new window, a large enough move, or time passed
+    fn worth_saving_over(&self, saved: &RateLimitBudget) -> bool {
+        let moved = |limit: Option<u64>, now: Option<u64>, then: Option<u64>| match (now, then) {
+            (Some(now), Some(then)) => match limit {
+                Some(limit) => now.abs_diff(then) as f64 >= limit as f64 * SAVE_DELTA_RATIO,
+                None => now != then,
+            },
+            (now, then) => now != then,
+        };
+        self.observed_at >= saved.observed_at + SAVE_INTERVAL_MS
+            || self.requests_reset != saved.requests_reset
+            || self.complexity_reset != saved.complexity_reset
+            || moved(
+                self.requests_limit,
+                self.requests_remaining,
+                saved.requests_remaining,
+            )
+            || moved(
+                self.complexity_limit,
+                self.complexity_remaining,
+                saved.complexity_remaining,
+            )
+    }
+
+    /// How long to pause before the next request, if the budget is running low
+    pub fn throttle_delay(&self, now_ms: u64) -> Option<Duration> {
+        let requests = pace(
+            self.requests_limit,
+            self.requests_remaining,
+            self.requests_reset,
+            1,
+            now_ms,
+        );
+        let complexity = pace(
+            self.complexity_limit,
+            self.complexity_remaining,
+            self.complexity_reset,
+            self.last_complexity.unwrap_or(1).max(1),
+            now_ms,
+        );
+        requests.max(complexity).map(|d| d.min(MAX_WAIT))
+    }
+}
+
+/// Spread what is left of one budget evenly over the rest of its window
+fn pace(
+    limit: Option<u64>,
+    remaining: Option<u64>,
+    reset_ms: Option<u64>,
+    cost: u64,
+    now_ms: u64,
+) -> Option<Duration> {
+    let (remaining, reset_ms) = (remaining?, reset_ms?);
+    if reset_ms <= now_ms {
+        return None;
+    }
+    if let Some(limit) = limit {
+        if remaining as f64 >= limit as f64 * LOW_BUDGET_RATIO {
+            return None;
+        }
+    }
+    let window = reset_ms - now_ms;
+    let calls_left = remaining / cost;
+    if calls_left == 0 {
+        return Some(Duration::from_millis(window));
+    }
+    Some(Duration::from_millis(window / (calls_left + 1)))
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn state() -> &'static Mutex<Option<RateLimitBudget>> {
+    static STATE: OnceLock<Mutex<Option<RateLimitBudget>>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(load().ok().flatten()))
+}
+
+fn budget_path() -> Result<PathBuf> {
+    Ok(cache::cache_dir_path()?.join(BUDGET_FILE))
+}
+
+fn load() -> Result<Option<RateLimitBudget>> {
+    let path = budget_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?).ok())
+}
+
+fn save(budget: &RateLimitBudget) -> Result<()> {
+    cache::write_atomic(&budget_path()?, serde_json::to_string(budget)?.as_bytes())
+}
+
+/// Remember the budget reported by a response
+pub fn record(headers: &HeaderMap) {
+    static SAVED: Mutex<Option<RateLimitBudget>> = Mutex::new(None);
+    let Some(budget) = RateLimitBudget::from_headers(headers) else {
+        return;
+    };
+    if let Ok(mut saved) = SAVED.lock() {
+        if saved
+            .as_ref()
+            .is_none_or(|saved| budget.worth_saving_over(saved))
+        {
+            // Persisting is best-effort; a read-only cache dir must not fail requests
+            let _ = save(&budget);
+            *saved = Some(budget.clone());
+        }
+    }
+    if let Ok(mut current) = state().lock() {
+        *current = Some(budget);
+    }
+}
+
+/// The most recently observed budget for this profile, if any
+pub fn current() -> Option<RateLimitBudget> {
+    state().lock().ok().and_then(|current| current.clone())
+}
+
+/// Pause if the last known budget is close to running out.
+///
+/// Callers queue on a lock while they wait, so concurrent requests (bulk
+/// commands fan out with `join_all`) are spaced out instead of all waking at
+/// once.
+pub async fn throttle() {
+    static QUEUE: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+    let _turn = QUEUE.lock().await;
+    let Some(delay) = current().and_then(|b| b.throttle_delay(now_ms())) else {
+        return;
+    };
+    if delay >= Duration::from_secs(1) {
+        eprintln!(
+            "{}",
+            format!(
+                "Rate limit budget low, waiting {:.1}s before the next request",
+                delay.as_secs_f64()
+            )
+            .dimmed()
+        );
+    }
+    tokio::time::sleep(delay).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn budget(remaining: u64, reset_in_ms: u64) -> RateLimitBudget {
+        RateLimitBudget {
+            requests_limit: Some(1000),
+            requests_remaining: Some(remaining),
+            requests_reset: Some(10_000 + reset_in_ms),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_small_moves_are_not_saved() {
+        let saved = budget(900, 60_000);
+        let next = |remaining: u64, after_ms: u64| RateLimitBudget {
+            observed_at: saved.observed_at + after_ms,
+            ..budget(remaining, 60_000)
+        };
+        assert!(!next(899, 100).worth_saving_over(&saved));
+        assert!(!next(851, 100).worth_saving_over(&saved));
+        // 5% of the 1000-request limit
+        assert!(next(850, 100).worth_saving_over(&saved));
+        assert!(next(899, SAVE_INTERVAL_MS).worth_saving_over(&saved));
+        // A new window
+        assert!(budget(999, 3_600_000).worth_saving_over(&saved));
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-requests-limit",
+            HeaderValue::from_static("1500"),
+        );
+        headers.insert(
+            "x-ratelimit-requests-remaining",
+            HeaderValue::from_static("1499"),
+        );
+        headers.insert("x-complexity", HeaderValue::from_static("12"));
+        let budget = RateLimitBudget::from_headers(&headers).unwrap();
+        assert_eq!(budget.requests_limit, Some(1500));
+        assert_eq!(budget.requests_remaining, Some(1499));
+        assert_eq!(budget.last_complexity, Some(12));
+        assert_eq!(budget.complexity_remaining, None);
+
+        assert!(RateLimitBudget::from_headers(&HeaderMap::new()).is_none());
+    }
+
+    #[test]
+    fn test_no_delay_with_healthy_budget() {
+        assert_eq!(budget(500, 60_000).throttle_delay(10_000), None);
+    }
+
+    #[test]
+    fn test_low_budget_spreads_remaining_requests() {
+        // 9 requests left over 10s: one every second
+        assert_eq!(
+            budget(9, 10_000).throttle_delay(10_000),
+            Some(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn test_exhausted_budget_waits_capped() {
+        assert_eq!(budget(0, 3_600_000).throttle_delay(10_000), Some(MAX_WAIT));
+        // Window already reset
+        assert_eq!(budget(0, 0).throttle_delay(20_000), None);
+    }
+
+    #[test]
+    fn test_complexity_budget_uses_last_cost() {
+        let budget = RateLimitBudget {
+            complexity_limit: Some(10_000),
+            complexity_remaining: Some(500),
+            complexity_reset: Some(20_000),
+            last_complexity: Some(100),
+            ..Default::default()
+        };
+        // 5 more calls of cost 100 fit in the last 10s
+        assert_eq!(
+            budget.throttle_delay(10_000),
+            Some(Duration::from_millis(10_000 / 6))
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! Record/replay of GraphQL traffic.
+//!
+//! In record mode every request/response pair is written to a directory as
+//! `<hash>-<n>.json`, where `hash` identifies the request (query with
+//! whitespace collapsed, plus variables) and `n` counts repeats of the same
+//! request within one process. Replay mode serves those files back instead of
+//! touching the network, so a captured session reproduces deterministically.
+//! Client-generated IDs for creates are part of the request, so they're kept
+//! in `client-ids.json` and handed out again in the same order on replay.
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::error::CliError;
+use crate::text::new_uuid;
+
+/// Response headers worth keeping in a recording
+const RECORDED_HEADERS: &[&str] = &["retry-after", "x-request-id"];
+
+/// Client IDs generated while recording, in the order they were handed out
+const CLIENT_IDS_FILE: &str = "client-ids.json";
+
+#[derive(Debug, Clone)]
+pub enum TrafficMode {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+/// A single recorded exchange
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Exchange {
+    pub request: Value,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+static MODE: OnceLock<TrafficMode> = OnceLock::new();
+static SEQUENCE: OnceLock<Mutex<HashMap<u64, usize>>> = OnceLock::new();
+static RECORDED_IDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static REPLAY_IDS: OnceLock<Vec<String>> = OnceLock::new();
+static REPLAY_ID_INDEX: AtomicUsize = AtomicUsize::new(0);
+
+pub fn set_mode(mode: TrafficMode) {
+    let _ = MODE.set(mode);
+}
+
+pub fn mode() -> Option<&'static TrafficMode> {
+    MODE.get()
+}
+
+pub fn is_replay() -> bool {
+    matches!(mode(), Some(TrafficMode::Replay(_)))
+}
+
+/// Stable identifier for a request body (FNV-1a over the normalized request)
+pub fn request_hash(request: &Value) -> u64 {
+    let query = request["query"]
+        .as_str()
+        .map(|q| q.split_whitespace().collect::<Vec<_>>().join(" "))
+        .unwrap_or_default();
+    let variables = request
+        .get("variables")
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in query.bytes().chain([0u8]).chain(variables.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// ID for a new entity. Random, except that recording writes each one to
+/// the capture and replay returns the recorded ones in order.
+pub fn client_id() -> String {
+    match mode() {
+        Some(TrafficMode::Record(dir)) => {
+            let id = new_uuid();
+            let mut ids = RECORDED_IDS.lock().unwrap_or_else(|e| e.into_inner());
+            ids.push(id.clone());
+            // A failed write surfaces when the request itself is recorded
+            if fs::create_dir_all(dir).is_ok() {
+                if let Ok(json) = serde_json::to_string_pretty(&*ids) {
+                    let _ = fs::write(dir.join(CLIENT_IDS_FILE), json);
+                }
+            }
+            id
+        }
+        Some(TrafficMode::Replay(dir)) => {
+            let ids = REPLAY_IDS.get_or_init(|| {
+                fs::read_to_string(dir.join(CLIENT_IDS_FILE))
+                    .ok()
+                    .and_then(|content| serde_json::from_str(&content).ok())
+                    .unwrap_or_default()
+            });
+            // Past the end of the capture the request can't match anyway
+            ids.get(REPLAY_ID_INDEX.fetch_add(1, Ordering::SeqCst))
+                .cloned()
+                .unwrap_or_else(new_uuid)
+        }
+        None => new_uuid(),
+    }
+}
+
+/// Next occurrence index for a request hash in this process
+fn next_sequence(hash: u64) -> usize {
+    let counters = SEQUENCE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut counters = counters.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = counters.entry(hash).or_insert(0);
+    let current = *entry;
+    *entry += 1;
+    current
+}
+
+fn exchange_path(dir: &Path, hash: u64, sequence: usize) -> PathBuf {
+    dir.join(format!("{:016x}-{}.json", hash, sequence))
+}
+
+/// Write a request/response pair to the recording directory
+pub fn record(
+    dir: &Path,
+    request: &Value,
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: &str,
+) -> Result<()> {
+    fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create recording directory {}", dir.display()))?;
+
+    let hash = request_hash(request);
+    let path = exchange_path(dir, hash, next_sequence(hash));
+    let headers = RECORDED_HEADERS
+        .iter()
+        .filter_map(|name| {
+            headers
+                .get(*name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| (name.to_string(), v.to_string()))
+        })
+        .collect();
+    let exchange = Exchange {
+        request: request.clone(),
+        status: status.as_u16(),
+        headers,
+        body: body.to_string(),
+    };
+
+    fs::write(&path, serde_json::to_string_pretty(&exchange)?)
+        .with_context(|| format!("Failed to write recording {}", path.display()))?;
+    Ok(())
+}
+
+/// Serve a recorded response for a request.
+///
+/// Repeats beyond what was recorded reuse the last recorded response, so
+/// polling commands keep working against a short capture.
+pub fn replay(dir: &Path, request: &Value) -> Result<(StatusCode, HeaderMap, String)> {
+    let hash = request_hash(request);
+    let sequence = next_sequence(hash);
+
+    let path = (0..=sequence)
+        .rev()
+        .map(|n| exchange_path(dir, hash, n))
+        .find(|p| p.exists())
+        .ok_or_else(|| {
+            CliError::new(1, format!("No recorded response for request {:016x}", hash))
+                .with_details(serde_json::json!({
+                    "replay_dir": dir.to_string_lossy(),
+                    "request": request,
+                }))
+        })?;
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read recording {}", path.display()))?;
+    let exchange: Exchange = serde_json::from_str(&content)
+        .with_context(|| format!("Invalid recording {}", path.display()))?;
+
+    let status = StatusCode::from_u16(exchange.status)
+        .with_context(|| format!("Invalid status in recording {}", path.display()))?;
+    let mut headers = HeaderMap::new();
+    for (name, value) in &exchange.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            headers.insert(name, value);
+        }
+    }
+
+    Ok((status, headers, exchange.body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_hash_ignores_query_whitespace() {
+        let a = json!({ "query": "query { viewer { id } }" });
+        let b = json!({ "query": "query {\n    viewer {\n        id\n    }\n}" });
+        assert_eq!(request_hash(&a), request_hash(&b));
+    }
+
+    #[test]
+    fn test_request_hash_depends_on_variables() {
+        let a = json!({ "query": "query($id: String!) { issue(id: $id) { id } }", "variables": { "id": "A-1" } });
+        let b = json!({ "query": "query($id: String!) { issue(id: $id) { id } }", "variables": { "id": "A-2" } });
+        assert_ne!(request_hash(&a), request_hash(&b));
+    }
+
+    #[test]
+    fn test_record_then_replay_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("linear-cli-recording-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let request = json!({ "query": "query { roundtrip }" });
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+        record(
+            &dir,
+            &request,
+            StatusCode::OK,
+            &headers,
+            r#"{"data":{"roundtrip":true}}"#,
+        )
+        .unwrap();
+
+        let (status, headers, body) = replay(&dir, &request).unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers.get("x-request-id").unwrap(), "req-1");
+        assert_eq!(body, r#"{"data":{"roundtrip":true}}"#);
+
+        // A repeat beyond the capture falls back to the last recorded response
+        assert!(replay(&dir, &request).is_ok());
+        assert!(replay(&dir, &json!({ "query": "query { missing }" })).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
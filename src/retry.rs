@@ -1,24 +1,85 @@
 use crate::error::CliError;
+use crate::trace;
 use rand::Rng;
-use std::time::Duration;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::time::sleep;
 
-/// Retry configuration for API calls
+/// What kind of failure an error is, which decides how (and whether) to retry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// Connection refused/reset, DNS failure, timeout
+    Network,
+    /// 5xx from the API
+    Server,
+    /// 429 or a RATELIMITED GraphQL error
+    RateLimited,
+    /// Anything retrying won't fix (auth, not found, invalid input)
+    Fatal,
+}
+
+/// Exponential backoff for one class of error
 #[derive(Debug, Clone, Copy)]
-pub struct RetryConfig {
-    pub max_retries: u32,
+pub struct BackoffPolicy {
     pub initial_delay_ms: u64,
     pub max_delay_ms: u64,
     pub exponential_base: f64,
 }
 
+impl BackoffPolicy {
+    /// Delay for a given attempt (0-indexed) with ±25% jitter
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        // Exponential backoff: initial_delay * base^attempt
+        let delay_ms = (self.initial_delay_ms as f64 * self.exponential_base.powi(attempt as i32))
+            .min(self.max_delay_ms as f64) as u64;
+
+        // Add ±25% jitter to avoid thundering herd
+        let jitter_range = (delay_ms / 4) as i64;
+        let jitter = if jitter_range > 0 {
+            rand::thread_rng().gen_range(-jitter_range..=jitter_range)
+        } else {
+            0
+        };
+        let final_delay = (delay_ms as i64 + jitter).max(0) as u64;
+
+        Duration::from_millis(final_delay)
+    }
+}
+
+/// Retry configuration for API calls
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    /// Network errors: retry quickly, the next attempt often just works
+    pub network: BackoffPolicy,
+    /// 5xx responses: back off further to let the API recover
+    pub server: BackoffPolicy,
+    /// 429s without a usable Retry-After
+    pub rate_limit: BackoffPolicy,
+    /// Stop retrying once waiting again would pass this much total time
+    pub max_elapsed_ms: u64,
+}
+
 impl Default for RetryConfig {
     fn default() -> Self {
         Self {
             max_retries: 3,
-            initial_delay_ms: 1000,
-            max_delay_ms: 30000,
-            exponential_base: 2.0,
+            network: BackoffPolicy {
+                initial_delay_ms: 500,
+                max_delay_ms: 10_000,
+                exponential_base: 2.0,
+            },
+            server: BackoffPolicy {
+                initial_delay_ms: 1000,
+                max_delay_ms: 30_000,
+                exponential_base: 2.0,
+            },
+            rate_limit: BackoffPolicy {
+                initial_delay_ms: 5000,
+                max_delay_ms: 60_000,
+                exponential_base: 2.0,
+            },
+            max_elapsed_ms: 120_000,
         }
     }
 }
@@ -38,80 +99,249 @@ impl RetryConfig {
         }
     }
 
-    /// Calculate delay for a given attempt (0-indexed) with jitter
-    pub fn delay_for_attempt(&self, attempt: u32, retry_after: Option<u64>) -> Duration {
-        // If server specified retry-after, use that
+    /// Delay before retrying an error of `class` after `attempt` (0-indexed)
+    /// failures; a server-provided Retry-After wins over the policy.
+    pub fn delay_for_attempt(
+        &self,
+        class: ErrorClass,
+        attempt: u32,
+        retry_after: Option<u64>,
+    ) -> Duration {
         if let Some(seconds) = retry_after {
             return Duration::from_secs(seconds);
         }
+        let policy = match class {
+            ErrorClass::Network => &self.network,
+            ErrorClass::RateLimited => &self.rate_limit,
+            ErrorClass::Server | ErrorClass::Fatal => &self.server,
+        };
+        policy.delay_for_attempt(attempt)
+    }
+}
 
-        // Exponential backoff: initial_delay * base^attempt
-        let delay_ms = (self.initial_delay_ms as f64 * self.exponential_base.powi(attempt as i32))
-            .min(self.max_delay_ms as f64) as u64;
+/// Parse a Retry-After header: delta seconds or an HTTP date
+/// (`Wed, 21 Oct 2015 07:28:00 GMT`). Dates in the past mean "now".
+pub fn parse_retry_after(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds);
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = date.timestamp() - chrono::Utc::now().timestamp();
+    Some(wait.max(0) as u64)
+}
 
-        // Add ±25% jitter to avoid thundering herd
-        let jitter_range = (delay_ms / 4) as i64;
-        let jitter = if jitter_range > 0 {
-            rand::thread_rng().gen_range(-jitter_range..=jitter_range)
-        } else {
-            0
-        };
-        let final_delay = (delay_ms as i64 + jitter).max(0) as u64;
+/// Consecutive network/server failures before the circuit opens
+const BREAKER_THRESHOLD: u32 = 5;
+
+/// How long an open circuit rejects calls before letting one through
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Fails fast once the API is clearly down.
+///
+/// Shared by every request in the process, so when a bulk command's
+/// concurrent futures start hitting connection errors or 5xx responses, the
+/// rest stop waiting on timeouts and backoff. After the cooldown a single
+/// call is let through as a probe while everyone else keeps failing fast;
+/// its success closes the circuit, another failure reopens it. A probe that
+/// never settles only holds the circuit for one more cooldown.
+pub struct CircuitBreaker {
+    failures: AtomicU32,
+    open_until_ms: AtomicU64,
+}
 
-        Duration::from_millis(final_delay)
+impl CircuitBreaker {
+    pub const fn new() -> Self {
+        Self {
+            failures: AtomicU32::new(0),
+            open_until_ms: AtomicU64::new(0),
+        }
+    }
+
+    fn now_ms() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0)
+    }
+
+    /// Error to return instead of calling the API, if the circuit is open
+    pub fn check(&self) -> Result<(), CliError> {
+        let open_until = self.open_until_ms.load(Ordering::SeqCst);
+        if open_until == 0 {
+            return Ok(());
+        }
+        let now = Self::now_ms();
+        if open_until <= now {
+            // Half-open: whoever moves the deadline first is the probe
+            let lease = now + BREAKER_COOLDOWN.as_millis() as u64;
+            if self
+                .open_until_ms
+                .compare_exchange(open_until, lease, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                return Ok(());
+            }
+            return self.check();
+        }
+        let wait = (open_until - now).div_ceil(1000);
+        Err(CliError::new(
+            1,
+            format!(
+                "Linear API unavailable after {} consecutive failures; not retrying for {}s",
+                self.failures.load(Ordering::SeqCst),
+                wait
+            ),
+        )
+        .with_retry_after(Some(wait)))
+    }
+
+    pub fn record_success(&self) {
+        self.failures.store(0, Ordering::SeqCst);
+        self.open_until_ms.store(0, Ordering::SeqCst);
+    }
+
+    pub fn record_failure(&self, class: ErrorClass) {
+        if !matches!(class, ErrorClass::Network | ErrorClass::Server) {
+            return;
+        }
+        let failures = self.failures.fetch_add(1, Ordering::SeqCst) + 1;
+        if failures >= BREAKER_THRESHOLD {
+            let until = Self::now_ms() + BREAKER_COOLDOWN.as_millis() as u64;
+            self.open_until_ms.store(until, Ordering::SeqCst);
+        }
     }
 }
 
+static BREAKER: CircuitBreaker = CircuitBreaker::new();
+
 /// Execute a function with retry logic
-pub async fn with_retry<F, Fut, T, E>(config: &RetryConfig, mut f: F) -> Result<T, E>
+pub async fn with_retry<F, Fut, T, E>(config: &RetryConfig, f: F) -> Result<T, E>
 where
     F: FnMut() -> Fut,
     Fut: std::future::Future<Output = Result<T, E>>,
-    E: std::fmt::Display + IsRetryable,
+    E: std::fmt::Display + IsRetryable + From<CliError>,
 {
-    let mut last_error: Option<E> = None;
-
-    for attempt in 0..=config.max_retries {
-        match f().await {
-            Ok(result) => return Ok(result),
-            Err(e) => {
-                if attempt < config.max_retries && e.is_retryable() {
-                    let retry_after = e.retry_after();
-                    let delay = config.delay_for_attempt(attempt, retry_after);
-                    eprintln!(
-                        "Attempt {} failed: {}. Retrying in {:?}...",
-                        attempt + 1,
-                        e,
-                        delay
-                    );
-                    sleep(delay).await;
-                    last_error = Some(e);
-                } else {
-                    return Err(e);
-                }
+    retry_with_breaker(config, &BREAKER, f).await
+}
+
+async fn retry_with_breaker<F, Fut, T, E>(
+    config: &RetryConfig,
+    breaker: &CircuitBreaker,
+    mut f: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+    E: std::fmt::Display + IsRetryable + From<CliError>,
+{
+    let started = Instant::now();
+    let budget = Duration::from_millis(config.max_elapsed_ms);
+    let mut attempt = 0;
+
+    loop {
+        breaker.check()?;
+        let e = match f().await {
+            Ok(result) => {
+                breaker.record_success();
+                return Ok(result);
             }
+            Err(e) => e,
+        };
+
+        let class = e.error_class();
+        breaker.record_failure(class);
+        if attempt >= config.max_retries || !e.is_retryable() {
+            return Err(e);
         }
-    }
 
-    Err(last_error.expect("Should have an error after retries"))
+        let delay = config.delay_for_attempt(class, attempt, e.retry_after());
+        if started.elapsed() + delay > budget {
+            return Err(e);
+        }
+        attempt += 1;
+        if trace::enabled() {
+            trace::retry(attempt, config.max_retries, delay, &e.to_string());
+        } else {
+            eprintln!(
+                "Attempt {} failed: {}. Retrying in {:?}...",
+                attempt, e, delay
+            );
+        }
+        sleep(delay).await;
+    }
 }
 
 /// Trait to determine if an error is retryable
 pub trait IsRetryable {
-    fn is_retryable(&self) -> bool;
+    fn error_class(&self) -> ErrorClass;
     fn retry_after(&self) -> Option<u64>;
+
+    fn is_retryable(&self) -> bool {
+        self.error_class() != ErrorClass::Fatal
+    }
+}
+
+/// An HTTP status quoted in a message with its reason, as in
+/// `HTTP 502 Bad Gateway`; bare numbers like `ENG-500` don't count
+fn status_in_message(msg: &str) -> Option<u16> {
+    let bytes = msg.as_bytes();
+    (0..bytes.len().saturating_sub(2)).find_map(|i| {
+        if i > 0 && bytes[i - 1].is_ascii_alphanumeric() {
+            return None;
+        }
+        if !bytes[i..i + 3].iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        let code: u16 = msg[i..i + 3].parse().ok()?;
+        let reason = reqwest::StatusCode::from_u16(code)
+            .ok()?
+            .canonical_reason()?;
+        msg[i + 3..]
+            .strip_prefix(' ')?
+            .to_lowercase()
+            .starts_with(&reason.to_lowercase())
+            .then_some(code)
+    })
+}
+
+/// Classify by message, for errors without a status or error kind
+fn class_from_message(msg: &str) -> ErrorClass {
+    match status_in_message(msg) {
+        Some(429) => return ErrorClass::RateLimited,
+        Some(500..=599) => return ErrorClass::Server,
+        Some(_) => return ErrorClass::Fatal,
+        None => {}
+    }
+    let msg = msg.to_lowercase();
+    if msg.contains("rate limit") {
+        ErrorClass::RateLimited
+    } else if msg.contains("temporarily unavailable") {
+        ErrorClass::Server
+    } else if msg.contains("timeout") || msg.contains("timed out") || msg.contains("connection") {
+        ErrorClass::Network
+    } else {
+        ErrorClass::Fatal
+    }
 }
 
 impl IsRetryable for CliError {
-    fn is_retryable(&self) -> bool {
-        let msg = self.message.to_lowercase();
-        self.code == 4
-            || msg.contains("rate limit")
-            || msg.contains("timeout")
-            || msg.contains("temporarily unavailable")
-            || msg.contains("503")
-            || msg.contains("502")
-            || msg.contains("504")
+    fn error_class(&self) -> ErrorClass {
+        if self.code == 4 {
+            return ErrorClass::RateLimited;
+        }
+        if self.code != 1 {
+            return ErrorClass::Fatal;
+        }
+        let status = self
+            .details
+            .as_ref()
+            .and_then(|d| d.get("status"))
+            .and_then(|s| s.as_u64());
+        match status {
+            Some(500..=599) => ErrorClass::Server,
+            _ => class_from_message(&self.message),
+        }
     }
 
     fn retry_after(&self) -> Option<u64> {
@@ -120,20 +350,24 @@ impl IsRetryable for CliError {
 }
 
 impl IsRetryable for anyhow::Error {
-    fn is_retryable(&self) -> bool {
+    fn error_class(&self) -> ErrorClass {
         if let Some(cli) = self.downcast_ref::<CliError>() {
-            return cli.is_retryable();
+            return cli.error_class();
+        }
+        if let Some(err) = self.downcast_ref::<reqwest::Error>() {
+            if err.is_timeout() || err.is_connect() || err.is_request() {
+                return ErrorClass::Network;
+            }
+            if let Some(status) = err.status() {
+                if status.as_u16() == 429 {
+                    return ErrorClass::RateLimited;
+                }
+                if status.is_server_error() {
+                    return ErrorClass::Server;
+                }
+            }
         }
-        let msg = self.to_string().to_lowercase();
-        // Retry on rate limits, timeouts, and transient network errors
-        msg.contains("rate limit")
-            || msg.contains("429")
-            || msg.contains("timeout")
-            || msg.contains("connection")
-            || msg.contains("temporarily unavailable")
-            || msg.contains("503")
-            || msg.contains("502")
-            || msg.contains("504")
+        class_from_message(&self.to_string())
     }
 
     fn retry_after(&self) -> Option<u64> {
@@ -150,8 +384,8 @@ mod tests {
     fn test_retry_config_default() {
         let config = RetryConfig::default();
         assert_eq!(config.max_retries, 3);
-        assert_eq!(config.initial_delay_ms, 1000);
-        assert_eq!(config.max_delay_ms, 30000);
+        assert_eq!(config.server.initial_delay_ms, 1000);
+        assert_eq!(config.server.max_delay_ms, 30000);
     }
 
     #[test]
@@ -169,46 +403,65 @@ mod tests {
     #[test]
     fn test_delay_with_retry_after() {
         let config = RetryConfig::default();
-        let delay = config.delay_for_attempt(0, Some(10));
+        let delay = config.delay_for_attempt(ErrorClass::RateLimited, 0, Some(10));
         assert_eq!(delay, Duration::from_secs(10));
     }
 
     #[test]
     fn test_delay_exponential_backoff() {
-        let config = RetryConfig {
-            max_retries: 3,
+        let policy = BackoffPolicy {
             initial_delay_ms: 1000,
             max_delay_ms: 30000,
             exponential_base: 2.0,
         };
 
         // Attempt 0: ~1000ms (with jitter)
-        let delay0 = config.delay_for_attempt(0, None);
+        let delay0 = policy.delay_for_attempt(0);
         assert!(delay0.as_millis() >= 750 && delay0.as_millis() <= 1250);
 
         // Attempt 1: ~2000ms (with jitter)
-        let delay1 = config.delay_for_attempt(1, None);
+        let delay1 = policy.delay_for_attempt(1);
         assert!(delay1.as_millis() >= 1500 && delay1.as_millis() <= 2500);
 
         // Attempt 2: ~4000ms (with jitter)
-        let delay2 = config.delay_for_attempt(2, None);
+        let delay2 = policy.delay_for_attempt(2);
         assert!(delay2.as_millis() >= 3000 && delay2.as_millis() <= 5000);
     }
 
     #[test]
     fn test_delay_capped_at_max() {
-        let config = RetryConfig {
-            max_retries: 10,
+        let policy = BackoffPolicy {
             initial_delay_ms: 1000,
             max_delay_ms: 5000,
             exponential_base: 2.0,
         };
 
         // Attempt 10 would be 1000 * 2^10 = 1024000ms, but should be capped
-        let delay = config.delay_for_attempt(10, None);
+        let delay = policy.delay_for_attempt(10);
         assert!(delay.as_millis() <= 6250); // max + 25% jitter
     }
 
+    #[test]
+    fn test_delay_policy_per_class() {
+        let config = RetryConfig::default();
+        let network = config.delay_for_attempt(ErrorClass::Network, 0, None);
+        let rate_limited = config.delay_for_attempt(ErrorClass::RateLimited, 0, None);
+        assert!(network.as_millis() <= 625);
+        assert!(rate_limited.as_millis() >= 3750);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(120));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), Some(0));
+        let future = (chrono::Utc::now() + chrono::Duration::seconds(90))
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string();
+        let wait = parse_retry_after(&future).unwrap();
+        assert!((88..=90).contains(&wait), "{}", wait);
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+
     #[test]
     fn test_cli_error_retryable() {
         let rate_limit = CliError::new(4, "Rate limit exceeded");
@@ -227,6 +480,21 @@ mod tests {
         assert!(!not_found.is_retryable());
     }
 
+    #[test]
+    fn test_cli_error_classes() {
+        let status = CliError::new(1, "HTTP 502 Bad Gateway")
+            .with_details(serde_json::json!({ "status": 502 }));
+        assert_eq!(status.error_class(), ErrorClass::Server);
+        assert_eq!(
+            CliError::new(4, "Rate limit exceeded").error_class(),
+            ErrorClass::RateLimited
+        );
+        assert_eq!(
+            CliError::new(5, "Invalid input").error_class(),
+            ErrorClass::Fatal
+        );
+    }
+
     #[test]
     fn test_cli_error_retry_after() {
         let err = CliError::new(4, "Rate limited").with_retry_after(Some(30));
@@ -235,4 +503,111 @@ mod tests {
         let err_no_retry = CliError::new(1, "Error");
         assert_eq!(err_no_retry.retry_after(), None);
     }
+
+    #[test]
+    fn test_circuit_breaker_opens_and_resets() {
+        let breaker = CircuitBreaker::new();
+        for _ in 0..BREAKER_THRESHOLD - 1 {
+            breaker.record_failure(ErrorClass::Server);
+        }
+        // Fatal errors say nothing about API health
+        breaker.record_failure(ErrorClass::Fatal);
+        assert!(breaker.check().is_ok());
+
+        breaker.record_failure(ErrorClass::Network);
+        let err = breaker.check().unwrap_err();
+        assert!(err.message.contains("unavailable"));
+        assert!(err.retry_after.is_some());
+
+        breaker.record_success();
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_circuit_breaker_half_open_lets_one_probe_through() {
+        let breaker = CircuitBreaker::new();
+        for _ in 0..BREAKER_THRESHOLD {
+            breaker.record_failure(ErrorClass::Server);
+        }
+        // Cooldown over
+        breaker.open_until_ms.store(1, Ordering::SeqCst);
+        assert!(breaker.check().is_ok());
+        assert!(breaker.check().is_err());
+
+        // The probe fails: open for another full cooldown
+        breaker.record_failure(ErrorClass::Server);
+        assert!(breaker.check().is_err());
+
+        breaker.open_until_ms.store(1, Ordering::SeqCst);
+        assert!(breaker.check().is_ok());
+        breaker.record_success();
+        assert!(breaker.check().is_ok());
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_class_from_message_matches_status_codes() {
+        assert_eq!(
+            class_from_message("HTTP 502 Bad Gateway"),
+            ErrorClass::Server
+        );
+        assert_eq!(
+            class_from_message("HTTP status server error (503 Service Unavailable) for url"),
+            ErrorClass::Server
+        );
+        assert_eq!(
+            class_from_message("HTTP 429 Too Many Requests"),
+            ErrorClass::RateLimited
+        );
+        assert_eq!(
+            class_from_message("HTTP 400 Bad Request"),
+            ErrorClass::Fatal
+        );
+        // Identifiers and counts that merely contain a status-like number
+        assert_eq!(
+            class_from_message("Issue ENG-500 not found"),
+            ErrorClass::Fatal
+        );
+        assert_eq!(class_from_message("Found 502 issues"), ErrorClass::Fatal);
+        assert_eq!(
+            class_from_message("Cycle 1429 is closed"),
+            ErrorClass::Fatal
+        );
+        assert_eq!(class_from_message("Request timed out"), ErrorClass::Network);
+    }
+
+    #[tokio::test]
+    async fn test_open_breaker_skips_call() {
+        let breaker = CircuitBreaker::new();
+        for _ in 0..BREAKER_THRESHOLD {
+            breaker.record_failure(ErrorClass::Server);
+        }
+        let mut calls = 0;
+        let result: Result<(), anyhow::Error> =
+            retry_with_breaker(&RetryConfig::new(3), &breaker, || {
+                calls += 1;
+                async { Ok(()) }
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(calls, 0);
+    }
+
+    #[tokio::test]
+    async fn test_time_budget_stops_retries() {
+        let config = RetryConfig {
+            max_elapsed_ms: 100,
+            ..RetryConfig::new(5)
+        };
+        let breaker = CircuitBreaker::new();
+        let mut calls = 0;
+        let result: Result<(), anyhow::Error> = retry_with_breaker(&config, &breaker, || {
+            calls += 1;
+            async { Err(CliError::new(4, "Rate limit exceeded").into()) }
+        })
+        .await;
+        assert!(result.is_err());
+        // The first rate-limit backoff (~5s) already exceeds the budget
+        assert_eq!(calls, 1);
+    }
 }
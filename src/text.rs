@@ -24,10 +24,35 @@ pub fn is_uuid(value: &str) -> bool {
     value.len() == 36 && value.matches("-").count() == 4
 }
 
+/// Random (version 4) UUID, for IDs the client assigns before creating
+pub fn new_uuid() -> String {
+    let mut bytes: [u8; 16] = rand::random();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_new_uuid_is_v4() {
+        let id = new_uuid();
+        assert!(is_uuid(&id));
+        assert_eq!(&id[14..15], "4");
+        assert!(matches!(&id[19..20], "8" | "9" | "a" | "b"));
+        assert_ne!(id, new_uuid());
+    }
+
     #[test]
     fn test_truncate_none() {
         assert_eq!(truncate("hello world", None), "hello world");
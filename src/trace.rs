@@ -0,0 +1,219 @@
+//! Request logging for `--verbose`, `--trace` and `--trace-file`.
+//!
+//! `--verbose` logs one line per HTTP attempt: operation, status, latency and
+//! Linear's `x-request-id`, plus a line for each retry. `--trace` adds the
+//! request variables with anything secret-looking redacted. Lines go to
+//! stderr, or are appended to `--trace-file` so stdout and stderr stay clean.
+
+use chrono::Local;
+use serde_json::Value;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TraceLevel {
+    Verbose,
+    Trace,
+}
+
+struct Tracer {
+    level: TraceLevel,
+    file: Option<Mutex<File>>,
+}
+
+static TRACER: OnceLock<Tracer> = OnceLock::new();
+
+/// Variable names whose values are never logged
+const SECRET_KEYS: &[&str] = &[
+    "apikey",
+    "authorization",
+    "password",
+    "secret",
+    "token",
+    "accesstoken",
+    "refreshtoken",
+    "clientsecret",
+];
+
+pub fn init(level: TraceLevel, file: Option<&Path>) -> anyhow::Result<()> {
+    let file = match file {
+        Some(path) => Some(Mutex::new(
+            OpenOptions::new().create(true).append(true).open(path)?,
+        )),
+        None => None,
+    };
+    let _ = TRACER.set(Tracer { level, file });
+    Ok(())
+}
+
+pub fn enabled() -> bool {
+    TRACER.get().is_some()
+}
+
+fn write_line(line: &str) {
+    let Some(tracer) = TRACER.get() else {
+        return;
+    };
+    let line = format!("[{}] {}", Local::now().format("%H:%M:%S%.3f"), line);
+    match &tracer.file {
+        Some(file) => {
+            if let Ok(mut file) = file.lock() {
+                let _ = writeln!(file, "{}", line);
+            }
+        }
+        None => eprintln!("{}", line),
+    }
+}
+
+/// Log one HTTP attempt. `status` is `None` when the request never got a
+/// response (connection refused, timeout).
+pub fn request(
+    body: &Value,
+    status: Option<u16>,
+    latency: Duration,
+    request_id: Option<&str>,
+    error: Option<&str>,
+) {
+    let Some(tracer) = TRACER.get() else {
+        return;
+    };
+    let query = body["query"].as_str().unwrap_or("");
+    let mut line = format!(
+        "{} status={} latency={}ms",
+        operation_name(query),
+        status
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        latency.as_millis()
+    );
+    if let Some(id) = request_id {
+        line.push_str(&format!(" request_id={}", id));
+    }
+    if let Some(error) = error {
+        line.push_str(&format!(" error=\"{}\"", error));
+    }
+    write_line(&line);
+
+    if tracer.level >= TraceLevel::Trace {
+        if let Some(vars) = body.get("variables") {
+            let vars = redact(vars);
+            write_line(&format!(
+                "  variables={}",
+                serde_json::to_string(&vars).unwrap_or_default()
+            ));
+        }
+    }
+}
+
+/// Log a retry scheduled by `with_retry`
+pub fn retry(attempt: u32, max_retries: u32, delay: Duration, error: &str) {
+    write_line(&format!(
+        "retry attempt={}/{} delay={}ms error=\"{}\"",
+        attempt,
+        max_retries,
+        delay.as_millis(),
+        error
+    ));
+}
+
+/// `query issues`, `mutation IssueCreate`, ... from a GraphQL document: the
+/// operation name when there is one, otherwise the first root field.
+pub fn operation_name(query: &str) -> String {
+    let text = query.trim_start();
+    let (kind, rest) = ["query", "mutation", "subscription"]
+        .iter()
+        .find_map(|k| text.strip_prefix(k).map(|rest| (*k, rest)))
+        .unwrap_or(("query", text));
+
+    let ident = |s: &str| -> String {
+        s.trim_start()
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+            .collect()
+    };
+
+    let name = ident(rest);
+    if !name.is_empty() {
+        return format!("{} {}", kind, name);
+    }
+
+    // Anonymous: skip variable definitions, then take the first root field,
+    // looking through an alias (`i0: issue(...)`)
+    let mut body = rest.trim_start();
+    if body.starts_with('(') {
+        body = body.split_once(')').map(|(_, b)| b).unwrap_or("");
+    }
+    let body = body.split_once('{').map(|(_, b)| b).unwrap_or("");
+    let mut field = ident(body);
+    let after = body.trim_start()[field.len()..].trim_start();
+    if let Some(aliased) = after.strip_prefix(':') {
+        field = ident(aliased);
+    }
+    if field.is_empty() {
+        kind.to_string()
+    } else {
+        format!("{} {}", kind, field)
+    }
+}
+
+/// Copy of `value` with secret-looking keys replaced by `[REDACTED]`
+pub fn redact(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| {
+                    let key = k.to_lowercase().replace(['_', '-'], "");
+                    if SECRET_KEYS.iter().any(|s| key.contains(s)) {
+                        (k.clone(), Value::String("[REDACTED]".to_string()))
+                    } else {
+                        (k.clone(), redact(v))
+                    }
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact).collect()),
+        _ => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_operation_name() {
+        assert_eq!(
+            operation_name("mutation IssueCreate($input: X!) { issueCreate { id } }"),
+            "mutation IssueCreate"
+        );
+        assert_eq!(
+            operation_name("\n  query($id: String!) {\n issue(id: $id) { id } }"),
+            "query issue"
+        );
+        assert_eq!(operation_name("{ viewer { id } }"), "query viewer");
+        assert_eq!(
+            operation_name("query($i0: String!) { i0: issue(id: $i0) { id } }"),
+            "query issue"
+        );
+        assert_eq!(operation_name(""), "query");
+    }
+
+    #[test]
+    fn test_redact_nested_secrets() {
+        let vars = json!({
+            "input": { "title": "T", "apiKey": "lin_api_x", "webhook_secret": "s" },
+            "list": [{ "accessToken": "t" }],
+            "first": 50
+        });
+        let redacted = redact(&vars);
+        assert_eq!(redacted["input"]["title"], "T");
+        assert_eq!(redacted["input"]["apiKey"], "[REDACTED]");
+        assert_eq!(redacted["input"]["webhook_secret"], "[REDACTED]");
+        assert_eq!(redacted["list"][0]["accessToken"], "[REDACTED]");
+        assert_eq!(redacted["first"], 50);
+    }
+}
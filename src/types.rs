@@ -58,6 +58,10 @@ pub struct Issue {
     pub parent: Option<Box<IssueRef>>,
     #[serde(default)]
     pub sub_issues: Option<IssueConnection>,
+    #[serde(default)]
+    pub attachments: Option<AttachmentConnection>,
+    #[serde(default)]
+    pub documents: Option<DocumentConnection>,
 }
 
 /// A minimal issue reference for parent/child relationships.
@@ -77,6 +81,26 @@ pub struct IssueConnection {
     pub nodes: Vec<IssueRef>,
 }
 
+/// A link attached to an issue.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+}
+
+/// Connection wrapper for attachments.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentConnection {
+    pub nodes: Vec<Attachment>,
+}
+
 /// A workflow state (issue status).
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -148,8 +172,14 @@ pub struct Project {
     #[serde(default)]
     pub state: Option<String>,
     #[serde(default)]
+    pub status: Option<ProjectStatus>,
+    #[serde(default)]
+    pub labels: Option<LabelConnection>,
+    #[serde(default)]
     pub progress: Option<f64>,
     #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
     pub target_date: Option<String>,
     #[serde(default)]
     pub started_at: Option<String>,
@@ -163,6 +193,17 @@ pub struct Project {
     pub updated_at: Option<String>,
 }
 
+/// A project status (replaces the legacy `state` string).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatus {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    #[serde(default)]
+    pub status_type: Option<String>,
+}
+
 /// A sprint cycle.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -254,6 +295,13 @@ pub struct Document {
     pub updated_at: Option<String>,
 }
 
+/// Connection wrapper for documents.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentConnection {
+    pub nodes: Vec<Document>,
+}
+
 /// A notification from Linear.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use std::process::Command;
 
+use crate::{config, project_config};
+
 pub fn run_git_command(args: &[&str]) -> Result<String> {
     let output = Command::new("git").args(args).output()?;
 
@@ -12,8 +14,8 @@ pub fn run_git_command(args: &[&str]) -> Result<String> {
     }
 }
 
-pub fn generate_branch_name(identifier: &str, title: &str) -> String {
-    // Convert title to kebab-case for branch name
+/// Kebab-case slug of an issue title, at most 50 characters
+fn slugify(title: &str) -> String {
     let slug: String = title
         .to_lowercase()
         .chars()
@@ -25,11 +27,47 @@ pub fn generate_branch_name(identifier: &str, title: &str) -> String {
         .join("-");
 
     // Truncate if too long
-    let slug = if slug.len() > 50 {
+    if slug.len() > 50 {
         slug[..50].trim_end_matches('-').to_string()
     } else {
         slug
-    };
+    }
+}
+
+pub fn generate_branch_name(identifier: &str, title: &str) -> String {
+    format!("{}/{}", identifier.to_lowercase(), slugify(title))
+}
 
-    format!("{}/{}", identifier.to_lowercase(), slug)
+/// Fill in a branch template: `{identifier}` (lowercased), `{team}`,
+/// `{number}` and `{title}` (kebab-case slug)
+pub fn render_branch_template(template: &str, identifier: &str, title: &str) -> String {
+    let identifier = identifier.to_lowercase();
+    let (team, number) = identifier.split_once('-').unwrap_or(("", &identifier));
+    template
+        .replace("{identifier}", &identifier)
+        .replace("{team}", team)
+        .replace("{number}", number)
+        .replace("{title}", &slugify(title))
+}
+
+/// Branch template from `.linear.toml`, else `defaults.branch_template` in config.toml
+pub fn branch_template() -> Option<String> {
+    project_config::get().branch_template.clone().or_else(|| {
+        config::load_config()
+            .ok()
+            .and_then(|c| c.defaults.branch_template)
+    })
+}
+
+/// Branch for an issue when none is given: the configured template,
+/// then Linear's suggested name, then one generated from the title
+pub fn issue_branch_name(identifier: &str, title: &str, linear_branch: &str) -> String {
+    if let Some(template) = branch_template() {
+        return render_branch_template(&template, identifier, title);
+    }
+    if linear_branch.is_empty() {
+        generate_branch_name(identifier, title)
+    } else {
+        linear_branch.to_string()
+    }
 }
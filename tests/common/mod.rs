@@ -0,0 +1,247 @@
+//! Local fake GraphQL server for exercising real command flows.
+//!
+//! The server speaks just enough HTTP/1.1 for reqwest: it reads each POST body,
+//! records it, and answers with whatever the test's handler returns. Point the
+//! CLI at it with `LINEAR_API_URL` (see `run_cli_against`).
+
+#![allow(dead_code)]
+
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+/// Response returned by a mock handler
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Value,
+}
+
+impl MockResponse {
+    pub fn status(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+impl From<Value> for MockResponse {
+    fn from(body: Value) -> Self {
+        Self::status(200, body)
+    }
+}
+
+type Handler = dyn Fn(&Value) -> MockResponse + Send + Sync;
+
+pub struct MockServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Value>>>,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Start a server on an ephemeral port; `handler` receives each GraphQL request body.
+    pub fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&Value) -> MockResponse + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+        let addr = listener.local_addr().expect("Failed to read mock address");
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let thread = {
+            let requests = Arc::clone(&requests);
+            let shutdown = Arc::clone(&shutdown);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    let Ok(stream) = stream else { continue };
+                    let requests = Arc::clone(&requests);
+                    let handler = Arc::clone(&handler);
+                    thread::spawn(move || serve_connection(stream, &requests, handler.as_ref()));
+                }
+            })
+        };
+
+        Self {
+            addr,
+            requests,
+            shutdown,
+            thread: Some(thread),
+        }
+    }
+
+    /// GraphQL endpoint URL for `LINEAR_API_URL`
+    pub fn url(&self) -> String {
+        format!("http://{}/graphql", self.addr)
+    }
+
+    /// All request bodies received so far, in arrival order
+    pub fn requests(&self) -> Vec<Value> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Request bodies whose query contains `needle`
+    pub fn requests_matching(&self, needle: &str) -> Vec<Value> {
+        self.requests()
+            .into_iter()
+            .filter(|r| query_contains(r, needle))
+            .collect()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it observes the shutdown flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Check whether a request body's GraphQL query contains `needle`
+pub fn query_contains(body: &Value, needle: &str) -> bool {
+    body["query"]
+        .as_str()
+        .map(|q| q.contains(needle))
+        .unwrap_or(false)
+}
+
+fn serve_connection(stream: TcpStream, requests: &Mutex<Vec<Value>>, handler: &Handler) {
+    let mut writer = match stream.try_clone() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let mut reader = BufReader::new(stream);
+
+    // Keep-alive: serve requests until the client closes the connection
+    loop {
+        let mut content_length = 0usize;
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => return,
+            Ok(_) => {}
+        }
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => return,
+                Ok(_) => {}
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+
+        let mut body = vec![0u8; content_length];
+        if reader.read_exact(&mut body).is_err() {
+            return;
+        }
+        let body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        requests.lock().unwrap().push(body.clone());
+
+        let response = handler(&body);
+        let payload = serde_json::to_vec(&response.body).unwrap_or_default();
+        let mut head = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+            response.status,
+            payload.len()
+        );
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str("\r\n");
+
+        if writer.write_all(head.as_bytes()).is_err() || writer.write_all(&payload).is_err() {
+            return;
+        }
+        let _ = writer.flush();
+    }
+}
+
+/// Create an empty, per-test home directory so config and cache stay isolated
+pub fn temp_home() -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "linear-cli-test-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::SeqCst)
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("Failed to create temp home");
+    dir
+}
+
+/// Build a CLI command pointed at `server` with an isolated home directory
+pub fn cli_command(server: &MockServer, home: &PathBuf) -> Command {
+    cli_command_for_url(&server.url(), home)
+}
+
+/// Build a CLI command pointed at an arbitrary endpoint with an isolated home directory
+pub fn cli_command_for_url(url: &str, home: &PathBuf) -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_linear-cli"));
+    cmd.env("LINEAR_API_URL", url)
+        .env("LINEAR_API_KEY", "lin_api_test")
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("APPDATA", home)
+        .env_remove("LINEAR_CLI_PROFILE")
+        .env_remove("LINEAR_CLI_OUTPUT")
+        .env_remove("LINEAR_CLI_CACHE_TTL")
+        .env_remove("LINEAR_CLI_CACHE_MAX_ENTRIES")
+        .env_remove("LINEAR_CLI_NO_CACHE")
+        .env_remove("LINEAR_CLI_RECORD")
+        .env_remove("LINEAR_CLI_REPLAY")
+        .env_remove("LINEAR_CLI_BATCH_SIZE")
+        .env_remove("LINEAR_CLI_TRACE")
+        .env_remove("LINEAR_CLI_TRACE_FILE")
+        .env_remove("LINEAR_CLI_PROXY")
+        .env_remove("LINEAR_CLI_CA_CERT")
+        .env_remove("LINEAR_CLI_TIMEOUT")
+        .env_remove("LINEAR_CLI_CONNECT_TIMEOUT")
+        .env_remove("LINEAR_CLI_OFFLINE")
+        .env_remove("LINEAR_CLI_NO_PROJECT_CONFIG")
+        .env_remove("LINEAR_CLI_PASSPHRASE");
+    cmd
+}
+
+/// Run the CLI against `server`, returning (exit code, stdout, stderr)
+pub fn run_cli_against(server: &MockServer, args: &[&str]) -> (i32, String, String) {
+    let home = temp_home();
+    let output = cli_command(server, &home)
+        .args(args)
+        .output()
+        .expect("Failed to execute command");
+    let _ = std::fs::remove_dir_all(&home);
+
+    (
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}